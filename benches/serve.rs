@@ -0,0 +1,197 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use criterion::{Criterion, Throughput};
+use http::{HeaderValue, Request, Response, header};
+use serde_json::json;
+use utoipa_scalar_warpper::{Scalar, ScalarService};
+
+const THREADS: usize = 8;
+
+// Counts what the handlers allocate, the timings alone hide how much memory every hit churns
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+// Large enough for the copy of the spec to show, about 1 MB like a sizeable real api
+fn service() -> ScalarService {
+    let paths = (0..2000)
+        .map(|index| {
+            let operation = json!({
+                "get": {
+                    "operationId": format!("get_item_{index}"),
+                    "description": "Returns the item with all of its fields and relations",
+                    "responses": { "200": { "description": "The item" } }
+                }
+            });
+            (format!("/items/{index}"), operation)
+        })
+        .collect::<serde_json::Map<_, _>>();
+    let openapi = json!({
+        "openapi": "3.1.0",
+        "info": { "title": "Bench", "version": "1.0.0" },
+        "paths": paths
+    });
+    Scalar::new(openapi).with_url("/scalar").into_service()
+}
+
+fn request(path: &str) -> Request<()> {
+    Request::get(path).body(()).unwrap()
+}
+
+// The bundle the crate embeds, which the script route used to serve as it is
+const SCALAR_API_REFERENCE_JS: &str = include_str!("../static/scalar-api-reference.js");
+
+// The routes before the bodies were shared: each one owned its rendered `String`, which went with
+// the handler that the framework clones or copies for every request. Axum served the bundle from
+// a `&'static str` already, rocket owned a `String` of it as well and copied the 3.5 MB per request
+#[derive(Clone, Copy)]
+enum Framework {
+    Axum,
+    Rocket,
+}
+
+struct Baseline {
+    content_type: HeaderValue,
+    body: Option<String>,
+}
+
+impl Baseline {
+    fn new(service: &ScalarService, path: &str, framework: Framework) -> Self {
+        let response = service.handle(&request(path)).unwrap();
+        let content_type = response.headers()[header::CONTENT_TYPE].clone();
+        let owned = match framework {
+            Framework::Axum => path != service.script_url(),
+            Framework::Rocket => true,
+        };
+        let body = owned.then(|| String::from_utf8(response.into_body().to_vec()).unwrap());
+        Self { content_type, body }
+    }
+
+    fn serve(&self) -> Response<Bytes> {
+        let body = match self.body.clone() {
+            Some(body) => Bytes::from(body),
+            None => Bytes::from_static(SCALAR_API_REFERENCE_JS.as_bytes()),
+        };
+        let mut response = Response::new(body);
+        let content_type = self.content_type.clone();
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, content_type);
+        response
+    }
+}
+
+fn baselines(service: &ScalarService, path: &str) -> [(&'static str, Baseline); 2] {
+    [
+        ("axum", Baseline::new(service, path, Framework::Axum)),
+        ("rocket", Baseline::new(service, path, Framework::Rocket)),
+    ]
+}
+
+fn count_allocations(requests: usize, mut serve: impl FnMut()) -> (usize, usize) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    for _ in 0..requests {
+        serve();
+    }
+    (
+        (ALLOCATIONS.load(Ordering::Relaxed) - allocations) / requests,
+        (ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes) / requests,
+    )
+}
+
+fn report_allocations(service: &ScalarService) {
+    for (name, path) in paths(service) {
+        let request = request(&path);
+        let (shared, shared_bytes) = count_allocations(1000, || {
+            black_box(service.handle(&request));
+        });
+        println!("allocations per {name} request, shared: {shared} ({shared_bytes} bytes)");
+        for (framework, baseline) in baselines(service, &path) {
+            let (old, old_bytes) = count_allocations(1000, || {
+                black_box(baseline.serve());
+            });
+            println!("allocations per {name} request, {framework}: {old} ({old_bytes} bytes)");
+        }
+    }
+}
+
+fn paths(service: &ScalarService) -> [(&'static str, String); 3] {
+    [
+        ("page", service.url().to_owned()),
+        ("script", service.script_url().to_owned()),
+        ("spec", service.api_json_url().to_owned()),
+    ]
+}
+
+fn under_load(iters: u64, serve: impl Fn() + Sync) -> Duration {
+    let per_thread = iters.div_ceil(THREADS as u64);
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..THREADS {
+            scope.spawn(|| {
+                for _ in 0..per_thread {
+                    serve();
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn bench_assets(c: &mut Criterion, service: &ScalarService) {
+    for (name, path) in paths(service) {
+        let request = request(&path);
+        let len = service.handle(&request).unwrap().body().len();
+        let mut group = c.benchmark_group(name);
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_function("shared", |b| b.iter(|| black_box(service.handle(&request))));
+        group.bench_function("shared under load", |b| {
+            b.iter_custom(|iters| {
+                under_load(iters, || {
+                    black_box(service.handle(&request));
+                })
+            })
+        });
+        for (framework, baseline) in baselines(service, &path) {
+            group.bench_function(framework, |b| b.iter(|| black_box(baseline.serve())));
+            group.bench_function(format!("{framework} under load"), |b| {
+                b.iter_custom(|iters| {
+                    under_load(iters, || {
+                        black_box(baseline.serve());
+                    })
+                })
+            });
+        }
+        group.finish();
+    }
+}
+
+fn main() {
+    let service = service();
+    report_allocations(&service);
+    let mut criterion = Criterion::default().configure_from_args();
+    bench_assets(&mut criterion, &service);
+    criterion.final_summary();
+}
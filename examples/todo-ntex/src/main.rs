@@ -0,0 +1,291 @@
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+
+use ntex::web::{self, App, HttpServer};
+use utoipa::{
+    Modify, OpenApi,
+    openapi::security::{ApiKey, ApiKeyValue, SecurityScheme},
+};
+use utoipa_scalar_warpper::Scalar;
+
+const TODO_TAG: &str = "todo";
+
+#[ntex::main]
+async fn main() -> std::io::Result<()> {
+    let store = Arc::new(todo::Store::default());
+
+    HttpServer::new(move || {
+        App::new()
+            .state(store.clone())
+            .service(
+                web::resource("/api/v1/todos")
+                    .route(web::get().to(todo::list_todos))
+                    .route(web::post().to(todo::create_todo)),
+            )
+            .service(web::resource("/api/v1/todos/search").to(todo::search_todos))
+            .service(
+                web::resource("/api/v1/todos/{id}")
+                    .route(web::put().to(todo::mark_done))
+                    .route(web::delete().to(todo::delete_todo)),
+            )
+            .service(
+                Scalar::new(ApiDoc::openapi())
+                    .with_url("/scalar")
+                    .with_title("TodoOpenApi"),
+            )
+    })
+    .bind((Ipv4Addr::LOCALHOST, 8085))?
+    .run()
+    .await
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        todo::list_todos,
+        todo::search_todos,
+        todo::create_todo,
+        todo::mark_done,
+        todo::delete_todo
+    ),
+    modifiers(&SecurityAddon),
+    tags(
+            (name = TODO_TAG, description = "Todo items management API")
+    )
+)]
+struct ApiDoc;
+
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "api_key",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("todo_apikey"))),
+            )
+        }
+    }
+}
+
+mod todo {
+    use std::sync::{Arc, Mutex};
+
+    use ntex::http::StatusCode;
+    use ntex::web::types::{Json, Path, Query, State};
+    use ntex::web::{HttpRequest, HttpResponse};
+    use serde::{Deserialize, Serialize};
+    use utoipa::{IntoParams, ToSchema};
+
+    use crate::TODO_TAG;
+
+    /// Save the to-do list information in the memory
+    pub(super) type Store = Mutex<Vec<Todo>>;
+
+    /// To-do list
+    #[derive(Serialize, Deserialize, ToSchema, Clone)]
+    pub(super) struct Todo {
+        /// Unique identifier
+        id: i32,
+        /// Description
+        #[schema(example = "Buy groceries")]
+        value: String,
+        /// Is completed
+        done: bool,
+    }
+
+    /// Todo Error
+    #[derive(Serialize, Deserialize, ToSchema)]
+    enum TodoError {
+        /// The to-do list already exists. There's a conflict
+        #[schema(example = "Todo already exists")]
+        Conflict(String),
+        /// No to-do items were found
+        #[schema(example = "The task is not found by id = 1")]
+        NotFound(String),
+        /// Unauthorized operation
+        #[schema(example = "Missing api key")]
+        Unauthorized(String),
+    }
+
+    /// Get all the to-do items
+    #[utoipa::path(
+        get,
+        path = "/api/v1/todos",
+        tag = TODO_TAG,
+        responses(
+            (status = 200, description = "List all todos successfully", body = [Todo])
+        )
+    )]
+    pub(super) async fn list_todos(store: State<Arc<Store>>) -> Json<Vec<Todo>> {
+        let todos = store.lock().unwrap().clone();
+
+        Json(todos)
+    }
+
+    /// Query parameters
+    #[derive(Deserialize, IntoParams)]
+    #[into_params(parameter_in = Query)]
+    pub(super) struct TodoSearchQuery {
+        /// Search by value, case-sensitive
+        value: String,
+        /// Search by status to see if it's completed
+        done: bool,
+    }
+
+    /// Search for to-do items through query parameters and return matching to-do items
+    #[utoipa::path(
+        get,
+        path = "/api/v1/todos/search",
+        tag = TODO_TAG,
+        params(
+            TodoSearchQuery
+        ),
+        responses(
+            (status = 200, description = "List matching todos by query", body = [Todo])
+        )
+    )]
+    pub(super) async fn search_todos(
+        store: State<Arc<Store>>,
+        query: Query<TodoSearchQuery>,
+    ) -> Json<Vec<Todo>> {
+        Json(
+            store
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|todo| {
+                    todo.value.to_lowercase() == query.value.to_lowercase()
+                        && todo.done == query.done
+                })
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Create a new to-do item. If the item already exists, it will fail with a 409 conflict
+    #[utoipa::path(
+        post,
+        path = "/api/v1/todos",
+        tag = TODO_TAG,
+        request_body = Todo,
+        responses(
+            (status = 201, description = "Todo item created successfully", body = Todo),
+            (status = 409, description = "Todo already exists", body = TodoError)
+        )
+    )]
+    pub(super) async fn create_todo(store: State<Arc<Store>>, todo: Json<Todo>) -> HttpResponse {
+        let todo = todo.into_inner();
+        let mut todos = store.lock().unwrap();
+
+        match todos
+            .iter()
+            .find(|existing_todo| existing_todo.id == todo.id)
+        {
+            Some(found) => HttpResponse::Conflict().json(&TodoError::Conflict(format!(
+                "todo already exists: {}",
+                found.id
+            ))),
+            None => {
+                todos.push(todo.clone());
+
+                HttpResponse::Created().json(&todo)
+            }
+        }
+    }
+
+    /// Mark the to-do items as completed
+    ///
+    /// Mark the to-do item as completed by the given id. If successful, only return status 200; If no to-do items are found, a status 404 will be returned
+    #[utoipa::path(
+        put,
+        path = "/api/v1/todos/{id}",
+        tag = TODO_TAG,
+        responses(
+            (status = 200, description = "Todo marked done successfully"),
+            (status = 404, description = "Todo not found")
+        ),
+        params(
+            ("id" = i32, Path, description = "Todo database id")
+        ),
+        security(
+            (), // <-- make optional authentication
+            ("api_key" = [])
+        )
+    )]
+    pub(super) async fn mark_done(
+        id: Path<i32>,
+        store: State<Arc<Store>>,
+        request: HttpRequest,
+    ) -> HttpResponse {
+        if check_api_key(false, &request).is_err() {
+            return HttpResponse::Unauthorized().finish();
+        }
+
+        let mut todos = store.lock().unwrap();
+
+        let status = todos
+            .iter_mut()
+            .find(|todo| todo.id == *id)
+            .map(|todo| {
+                todo.done = true;
+                StatusCode::OK
+            })
+            .unwrap_or(StatusCode::NOT_FOUND);
+        HttpResponse::new(status)
+    }
+
+    /// Delete the to-do items
+    ///
+    /// Delete the to-do items from the memory storage by id.
+    /// If the corresponding to-do item is not found, a 404 will be returned.
+    /// If there is no permission to delete, return 401; If the deletion is successful, 200 will be returned
+    #[utoipa::path(
+        delete,
+        path = "/api/v1/todos/{id}",
+        tag = TODO_TAG,
+        responses(
+            (status = 200, description = "Todo marked done successfully"),
+            (status = 401, description = "Unauthorized to delete Todo", body = TodoError, example = json!(TodoError::Unauthorized(String::from("missing api key")))),
+            (status = 404, description = "Todo not found", body = TodoError, example = json!(TodoError::NotFound(String::from("id = 1"))))
+        ),
+        params(
+            ("id" = i32, Path, description = "Todo database id")
+        ),
+        security(
+            ("api_key" = [])
+        )
+    )]
+    pub(super) async fn delete_todo(
+        id: Path<i32>,
+        store: State<Arc<Store>>,
+        request: HttpRequest,
+    ) -> HttpResponse {
+        if let Err(response) = check_api_key(true, &request) {
+            return response;
+        }
+
+        let mut todos = store.lock().unwrap();
+
+        let len = todos.len();
+
+        todos.retain(|todo| todo.id != *id);
+
+        if todos.len() != len {
+            HttpResponse::Ok().finish()
+        } else {
+            HttpResponse::NotFound().json(&TodoError::NotFound(format!("id = {}", *id)))
+        }
+    }
+
+    /// Create an intermediate function for checking the api
+    fn check_api_key(require_api_key: bool, request: &HttpRequest) -> Result<(), HttpResponse> {
+        match request.headers().get("todo_apikey") {
+            Some(header) if header != "utoipa-rocks" => Err(HttpResponse::Unauthorized()
+                .json(&TodoError::Unauthorized(String::from("incorrect api key")))),
+            None if require_api_key => Err(HttpResponse::Unauthorized()
+                .json(&TodoError::Unauthorized(String::from("missing api key")))),
+            _ => Ok(()),
+        }
+    }
+}
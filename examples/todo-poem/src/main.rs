@@ -0,0 +1,309 @@
+use std::io::Error;
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+
+use poem::listener::TcpListener;
+use poem::middleware::AddData;
+use poem::{EndpointExt, Route, Server, get, put};
+use utoipa::{
+    Modify, OpenApi,
+    openapi::security::{ApiKey, ApiKeyValue, SecurityScheme},
+};
+use utoipa_scalar_warpper::Scalar;
+
+const TODO_TAG: &str = "todo";
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let store = Arc::new(todo::Store::default());
+
+    let app = Route::new()
+        .at(
+            "/api/v1/todos",
+            get(todo::list_todos).post(todo::create_todo),
+        )
+        .at("/api/v1/todos/search", get(todo::search_todos))
+        .at(
+            "/api/v1/todos/:id",
+            put(todo::mark_done).delete(todo::delete_todo),
+        )
+        .nest(
+            "/",
+            Route::from(
+                Scalar::new(ApiDoc::openapi())
+                    .with_url("/scalar")
+                    .with_title("TodoOpenApi"),
+            ),
+        )
+        .with(AddData::new(store));
+
+    Server::new(TcpListener::bind((Ipv4Addr::LOCALHOST, 8082)))
+        .run(app)
+        .await
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        todo::list_todos,
+        todo::search_todos,
+        todo::create_todo,
+        todo::mark_done,
+        todo::delete_todo
+    ),
+    modifiers(&SecurityAddon),
+    tags(
+            (name = TODO_TAG, description = "Todo items management API")
+    )
+)]
+struct ApiDoc;
+
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "api_key",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("todo_apikey"))),
+            )
+        }
+    }
+}
+
+mod todo {
+    use std::sync::{Arc, Mutex};
+
+    use poem::http::{HeaderMap, StatusCode};
+    use poem::web::{Data, Json, Path, Query};
+    use poem::{IntoResponse, Response, handler};
+    use serde::{Deserialize, Serialize};
+    use utoipa::{IntoParams, ToSchema};
+
+    use crate::TODO_TAG;
+
+    /// Save the to-do list information in the memory
+    pub(super) type Store = Mutex<Vec<Todo>>;
+
+    /// To-do list
+    #[derive(Serialize, Deserialize, ToSchema, Clone)]
+    pub(super) struct Todo {
+        /// Unique identifier
+        id: i32,
+        /// Description
+        #[schema(example = "Buy groceries")]
+        value: String,
+        /// Is completed
+        done: bool,
+    }
+
+    /// Todo Error
+    #[derive(Serialize, Deserialize, ToSchema)]
+    enum TodoError {
+        /// The to-do list already exists. There's a conflict
+        #[schema(example = "Todo already exists")]
+        Conflict(String),
+        /// No to-do items were found
+        #[schema(example = "The task is not found by id = 1")]
+        NotFound(String),
+        /// Unauthorized operation
+        #[schema(example = "Missing api key")]
+        Unauthorized(String),
+    }
+
+    /// Get all the to-do items
+    #[utoipa::path(
+        get,
+        path = "/api/v1/todos",
+        tag = TODO_TAG,
+        responses(
+            (status = 200, description = "List all todos successfully", body = [Todo])
+        )
+    )]
+    #[handler]
+    pub(super) fn list_todos(store: Data<&Arc<Store>>) -> Json<Vec<Todo>> {
+        let todos = store.lock().unwrap().clone();
+
+        Json(todos)
+    }
+
+    /// Query parameters
+    #[derive(Deserialize, IntoParams)]
+    #[into_params(parameter_in = Query)]
+    pub(super) struct TodoSearchQuery {
+        /// Search by value, case-sensitive
+        value: String,
+        /// Search by status to see if it's completed
+        done: bool,
+    }
+
+    /// Search for to-do items through query parameters and return matching to-do items
+    #[utoipa::path(
+        get,
+        path = "/api/v1/todos/search",
+        tag = TODO_TAG,
+        params(
+            TodoSearchQuery
+        ),
+        responses(
+            (status = 200, description = "List matching todos by query", body = [Todo])
+        )
+    )]
+    #[handler]
+    pub(super) fn search_todos(
+        store: Data<&Arc<Store>>,
+        Query(query): Query<TodoSearchQuery>,
+    ) -> Json<Vec<Todo>> {
+        Json(
+            store
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|todo| {
+                    todo.value.to_lowercase() == query.value.to_lowercase()
+                        && todo.done == query.done
+                })
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Create a new to-do item. If the item already exists, it will fail with a 409 conflict
+    #[utoipa::path(
+        post,
+        path = "/api/v1/todos",
+        tag = TODO_TAG,
+        request_body = Todo,
+        responses(
+            (status = 201, description = "Todo item created successfully", body = Todo),
+            (status = 409, description = "Todo already exists", body = TodoError)
+        )
+    )]
+    #[handler]
+    pub(super) fn create_todo(store: Data<&Arc<Store>>, Json(todo): Json<Todo>) -> Response {
+        let mut todos = store.lock().unwrap();
+
+        todos
+            .iter_mut()
+            .find(|existing_todo| existing_todo.id == todo.id)
+            .map(|found| {
+                Json(TodoError::Conflict(format!(
+                    "todo already exists: {}",
+                    found.id
+                )))
+                .with_status(StatusCode::CONFLICT)
+                .into_response()
+            })
+            .unwrap_or_else(|| {
+                todos.push(todo.clone());
+
+                Json(todo).with_status(StatusCode::CREATED).into_response()
+            })
+    }
+
+    /// Mark the to-do items as completed
+    ///
+    /// Mark the to-do item as completed by the given id. If successful, only return status 200; If no to-do items are found, a status 404 will be returned
+    #[utoipa::path(
+        put,
+        path = "/api/v1/todos/{id}",
+        tag = TODO_TAG,
+        responses(
+            (status = 200, description = "Todo marked done successfully"),
+            (status = 404, description = "Todo not found")
+        ),
+        params(
+            ("id" = i32, Path, description = "Todo database id")
+        ),
+        security(
+            (), // <-- make optional authentication
+            ("api_key" = [])
+        )
+    )]
+    #[handler]
+    pub(super) fn mark_done(
+        Path(id): Path<i32>,
+        store: Data<&Arc<Store>>,
+        headers: &HeaderMap,
+    ) -> StatusCode {
+        match check_api_key(false, headers) {
+            Ok(_) => (),
+            Err(_) => return StatusCode::UNAUTHORIZED,
+        }
+
+        let mut todos = store.lock().unwrap();
+
+        todos
+            .iter_mut()
+            .find(|todo| todo.id == id)
+            .map(|todo| {
+                todo.done = true;
+                StatusCode::OK
+            })
+            .unwrap_or(StatusCode::NOT_FOUND)
+    }
+
+    /// Delete the to-do items
+    ///
+    /// Delete the to-do items from the memory storage by id.
+    /// If the corresponding to-do item is not found, a 404 will be returned.
+    /// If there is no permission to delete, return 401; If the deletion is successful, 200 will be returned
+    #[utoipa::path(
+        delete,
+        path = "/api/v1/todos/{id}",
+        tag = TODO_TAG,
+        responses(
+            (status = 200, description = "Todo marked done successfully"),
+            (status = 401, description = "Unauthorized to delete Todo", body = TodoError, example = json!(TodoError::Unauthorized(String::from("missing api key")))),
+            (status = 404, description = "Todo not found", body = TodoError, example = json!(TodoError::NotFound(String::from("id = 1"))))
+        ),
+        params(
+            ("id" = i32, Path, description = "Todo database id")
+        ),
+        security(
+            ("api_key" = [])
+        )
+    )]
+    #[handler]
+    pub(super) fn delete_todo(
+        Path(id): Path<i32>,
+        store: Data<&Arc<Store>>,
+        headers: &HeaderMap,
+    ) -> Response {
+        if let Err((status, error)) = check_api_key(true, headers) {
+            return error.with_status(status).into_response();
+        }
+
+        let mut todos = store.lock().unwrap();
+
+        let len = todos.len();
+
+        todos.retain(|todo| todo.id != id);
+
+        if todos.len() != len {
+            StatusCode::OK.into_response()
+        } else {
+            Json(TodoError::NotFound(format!("id = {id}")))
+                .with_status(StatusCode::NOT_FOUND)
+                .into_response()
+        }
+    }
+
+    /// Create an intermediate function for checking the api
+    fn check_api_key(
+        require_api_key: bool,
+        headers: &HeaderMap,
+    ) -> Result<(), (StatusCode, Json<TodoError>)> {
+        match headers.get("todo_apikey") {
+            Some(header) if header != "utoipa-rocks" => Err((
+                StatusCode::UNAUTHORIZED,
+                Json(TodoError::Unauthorized(String::from("incorrect api key"))),
+            )),
+            None if require_api_key => Err((
+                StatusCode::UNAUTHORIZED,
+                Json(TodoError::Unauthorized(String::from("missing api key"))),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+
+use salvo::affix_state;
+use salvo::prelude::{Listener, Router, Server, TcpListener};
+use utoipa::{
+    Modify, OpenApi,
+    openapi::security::{ApiKey, ApiKeyValue, SecurityScheme},
+};
+use utoipa_scalar_warpper::Scalar;
+
+const TODO_TAG: &str = "todo";
+
+#[tokio::main]
+async fn main() {
+    let store = Arc::new(todo::Store::default());
+
+    let router = Router::new()
+        .hoop(affix_state::inject(store))
+        .push(
+            Router::with_path("api/v1/todos")
+                .get(todo::list_todos)
+                .post(todo::create_todo)
+                .push(Router::with_path("search").get(todo::search_todos))
+                .push(
+                    Router::with_path("{id}")
+                        .put(todo::mark_done)
+                        .delete(todo::delete_todo),
+                ),
+        )
+        .push(Router::from(
+            Scalar::new(ApiDoc::openapi())
+                .with_url("/scalar")
+                .with_title("TodoOpenApi"),
+        ));
+
+    let acceptor = TcpListener::new((Ipv4Addr::LOCALHOST, 8084)).bind().await;
+    Server::new(acceptor).serve(router).await;
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        todo::list_todos,
+        todo::search_todos,
+        todo::create_todo,
+        todo::mark_done,
+        todo::delete_todo
+    ),
+    modifiers(&SecurityAddon),
+    tags(
+            (name = TODO_TAG, description = "Todo items management API")
+    )
+)]
+struct ApiDoc;
+
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "api_key",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("todo_apikey"))),
+            )
+        }
+    }
+}
+
+mod todo {
+    use std::sync::{Arc, Mutex};
+
+    use salvo::http::{HeaderMap, StatusCode};
+    use salvo::prelude::{Depot, Json, Request, Response, handler};
+    use serde::{Deserialize, Serialize};
+    use utoipa::{IntoParams, ToSchema};
+
+    use crate::TODO_TAG;
+
+    /// Save the to-do list information in the memory
+    pub(super) type Store = Mutex<Vec<Todo>>;
+
+    /// To-do list
+    #[derive(Serialize, Deserialize, ToSchema, Clone)]
+    pub(super) struct Todo {
+        /// Unique identifier
+        id: i32,
+        /// Description
+        #[schema(example = "Buy groceries")]
+        value: String,
+        /// Is completed
+        done: bool,
+    }
+
+    /// Todo Error
+    #[derive(Serialize, Deserialize, ToSchema)]
+    enum TodoError {
+        /// The to-do list already exists. There's a conflict
+        #[schema(example = "Todo already exists")]
+        Conflict(String),
+        /// No to-do items were found
+        #[schema(example = "The task is not found by id = 1")]
+        NotFound(String),
+        /// Unauthorized operation
+        #[schema(example = "Missing api key")]
+        Unauthorized(String),
+    }
+
+    fn store(depot: &Depot) -> &Arc<Store> {
+        depot.obtain::<Arc<Store>>().unwrap()
+    }
+
+    /// Get all the to-do items
+    #[utoipa::path(
+        get,
+        path = "/api/v1/todos",
+        tag = TODO_TAG,
+        responses(
+            (status = 200, description = "List all todos successfully", body = [Todo])
+        )
+    )]
+    #[handler]
+    pub(super) fn list_todos(depot: &mut Depot) -> Json<Vec<Todo>> {
+        let todos = store(depot).lock().unwrap().clone();
+
+        Json(todos)
+    }
+
+    /// Query parameters
+    #[derive(Deserialize, IntoParams)]
+    #[into_params(parameter_in = Query)]
+    pub(super) struct TodoSearchQuery {
+        /// Search by value, case-sensitive
+        value: String,
+        /// Search by status to see if it's completed
+        done: bool,
+    }
+
+    /// Search for to-do items through query parameters and return matching to-do items
+    #[utoipa::path(
+        get,
+        path = "/api/v1/todos/search",
+        tag = TODO_TAG,
+        params(
+            TodoSearchQuery
+        ),
+        responses(
+            (status = 200, description = "List matching todos by query", body = [Todo])
+        )
+    )]
+    #[handler]
+    pub(super) fn search_todos(request: &mut Request, depot: &mut Depot, response: &mut Response) {
+        let Ok(query) = request.parse_queries::<TodoSearchQuery>() else {
+            response.status_code(StatusCode::BAD_REQUEST);
+            return;
+        };
+        let todos = store(depot)
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|todo| {
+                todo.value.to_lowercase() == query.value.to_lowercase() && todo.done == query.done
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        response.render(Json(todos));
+    }
+
+    /// Create a new to-do item. If the item already exists, it will fail with a 409 conflict
+    #[utoipa::path(
+        post,
+        path = "/api/v1/todos",
+        tag = TODO_TAG,
+        request_body = Todo,
+        responses(
+            (status = 201, description = "Todo item created successfully", body = Todo),
+            (status = 409, description = "Todo already exists", body = TodoError)
+        )
+    )]
+    #[handler]
+    pub(super) async fn create_todo(
+        request: &mut Request,
+        depot: &mut Depot,
+        response: &mut Response,
+    ) {
+        let Ok(todo) = request.parse_json::<Todo>().await else {
+            response.status_code(StatusCode::BAD_REQUEST);
+            return;
+        };
+        let mut todos = store(depot).lock().unwrap();
+
+        match todos
+            .iter()
+            .find(|existing_todo| existing_todo.id == todo.id)
+        {
+            Some(found) => {
+                response.status_code(StatusCode::CONFLICT);
+                response.render(Json(TodoError::Conflict(format!(
+                    "todo already exists: {}",
+                    found.id
+                ))));
+            }
+            None => {
+                todos.push(todo.clone());
+                response.status_code(StatusCode::CREATED);
+                response.render(Json(todo));
+            }
+        }
+    }
+
+    /// Mark the to-do items as completed
+    ///
+    /// Mark the to-do item as completed by the given id. If successful, only return status 200; If no to-do items are found, a status 404 will be returned
+    #[utoipa::path(
+        put,
+        path = "/api/v1/todos/{id}",
+        tag = TODO_TAG,
+        responses(
+            (status = 200, description = "Todo marked done successfully"),
+            (status = 404, description = "Todo not found")
+        ),
+        params(
+            ("id" = i32, Path, description = "Todo database id")
+        ),
+        security(
+            (), // <-- make optional authentication
+            ("api_key" = [])
+        )
+    )]
+    #[handler]
+    pub(super) fn mark_done(request: &mut Request, depot: &mut Depot, response: &mut Response) {
+        if check_api_key(false, request.headers()).is_err() {
+            response.status_code(StatusCode::UNAUTHORIZED);
+            return;
+        }
+        let Some(id) = request.param::<i32>("id") else {
+            response.status_code(StatusCode::BAD_REQUEST);
+            return;
+        };
+
+        let mut todos = store(depot).lock().unwrap();
+
+        let status = todos
+            .iter_mut()
+            .find(|todo| todo.id == id)
+            .map(|todo| {
+                todo.done = true;
+                StatusCode::OK
+            })
+            .unwrap_or(StatusCode::NOT_FOUND);
+        response.status_code(status);
+    }
+
+    /// Delete the to-do items
+    ///
+    /// Delete the to-do items from the memory storage by id.
+    /// If the corresponding to-do item is not found, a 404 will be returned.
+    /// If there is no permission to delete, return 401; If the deletion is successful, 200 will be returned
+    #[utoipa::path(
+        delete,
+        path = "/api/v1/todos/{id}",
+        tag = TODO_TAG,
+        responses(
+            (status = 200, description = "Todo marked done successfully"),
+            (status = 401, description = "Unauthorized to delete Todo", body = TodoError, example = json!(TodoError::Unauthorized(String::from("missing api key")))),
+            (status = 404, description = "Todo not found", body = TodoError, example = json!(TodoError::NotFound(String::from("id = 1"))))
+        ),
+        params(
+            ("id" = i32, Path, description = "Todo database id")
+        ),
+        security(
+            ("api_key" = [])
+        )
+    )]
+    #[handler]
+    pub(super) fn delete_todo(request: &mut Request, depot: &mut Depot, response: &mut Response) {
+        if let Err((status, error)) = check_api_key(true, request.headers()) {
+            response.status_code(status);
+            response.render(error);
+            return;
+        }
+        let Some(id) = request.param::<i32>("id") else {
+            response.status_code(StatusCode::BAD_REQUEST);
+            return;
+        };
+
+        let mut todos = store(depot).lock().unwrap();
+
+        let len = todos.len();
+
+        todos.retain(|todo| todo.id != id);
+
+        if todos.len() == len {
+            response.status_code(StatusCode::NOT_FOUND);
+            response.render(Json(TodoError::NotFound(format!("id = {id}"))));
+        }
+    }
+
+    /// Create an intermediate function for checking the api
+    fn check_api_key(
+        require_api_key: bool,
+        headers: &HeaderMap,
+    ) -> Result<(), (StatusCode, Json<TodoError>)> {
+        match headers.get("todo_apikey") {
+            Some(header) if header != "utoipa-rocks" => Err((
+                StatusCode::UNAUTHORIZED,
+                Json(TodoError::Unauthorized(String::from("incorrect api key"))),
+            )),
+            None if require_api_key => Err((
+                StatusCode::UNAUTHORIZED,
+                Json(TodoError::Unauthorized(String::from("missing api key"))),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
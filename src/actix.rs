@@ -1,51 +1,122 @@
-use crate::{SCALAR_API_REFERENCE_JS, Scalar, Serialize};
 use actix_web::dev::HttpServiceFactory;
-use actix_web::guard::Get;
+use actix_web::http::StatusCode;
 use actix_web::web::Data;
-use actix_web::{HttpResponse, Resource, Responder};
+use actix_web::{HttpRequest, HttpResponse, Resource};
+
+use crate::rebased::Rebased;
+use crate::service::to_request;
+use crate::{Scalar, Serialize};
 
 impl<S: Serialize> HttpServiceFactory for Scalar<S> {
     fn register(self, config: &mut actix_web::dev::AppService) {
-        let markup = self.markup();
-        let scalar_url = self.url.as_ref();
-        let script_url = self.script_url();
-        let api_json = self.api_json();
-        let api_json_url = self.api_json_url();
-
-        async fn serve_scalar(scalar: Data<String>) -> impl Responder {
-            HttpResponse::Ok()
-                .content_type("text/html")
-                .body(scalar.to_string())
+        let service = self.into_service();
+        for path in service.paths() {
+            Resource::new(path.as_str())
+                .app_data(Data::new(ScalarResource {
+                    service: Rebased::new(service.clone()),
+                    path,
+                }))
+                .to(serve_scalar)
+                .register(config);
+        }
+    }
+}
+
+// `AppService` does not tell which scope the resource is registered in, but the matched path
+// does: it is the prefix of the scope followed by the path of the resource
+struct ScalarResource {
+    service: Rebased,
+    path: String,
+}
+
+// The resource path tells the service which part of the original uri belongs to an enclosing scope
+async fn serve_scalar(request: HttpRequest, resource: Data<ScalarResource>) -> HttpResponse {
+    let prefix = request.path().strip_suffix(resource.path.as_str());
+    let service = resource.service.service(prefix.unwrap_or_default());
+    let uri = request.uri().to_string();
+    let authority = request
+        .uri()
+        .authority()
+        .map(|authority| authority.as_str());
+    let headers = request
+        .headers()
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_bytes()));
+    let method = request.method().as_str();
+    let response = to_request(method, &uri, &resource.path, authority, headers)
+        .and_then(|request| service.handle(&request));
+    let Some(response) = response else {
+        return HttpResponse::NotFound().finish();
+    };
+    let status = StatusCode::from_u16(response.status().as_u16()).unwrap();
+    let mut builder = HttpResponse::build(status);
+    for (name, value) in response.headers() {
+        if name != http::header::CONTENT_LENGTH {
+            builder.insert_header((name.as_str(), value.as_bytes()));
+        }
+    }
+    builder.body(response.into_body())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::{App, test, web};
+    use http::Request;
+
+    use crate::{Scalar, Tenant, TenantResolver};
+
+    struct PathTenants;
+
+    impl TenantResolver for PathTenants {
+        fn tenant_id(&self, request: &Request<()>) -> Option<String> {
+            request.uri().path().split('/').nth(1).map(str::to_owned)
         }
 
-        async fn serve_scalar_api_js(api_js: Data<&str>) -> impl Responder {
-            HttpResponse::Ok()
-                .content_type("application/javascript")
-                .body(api_js.to_string())
+        fn resolve(&self, tenant_id: &str) -> Option<Tenant> {
+            let openapi = serde_json::json!({ "tenant": tenant_id });
+            (tenant_id == "acme").then(|| Tenant::new(Scalar::new(openapi).with_openapi_30()))
         }
+    }
 
-        async fn serve_scalar_api_json(api_json: Data<String>) -> impl Responder {
-            HttpResponse::Ok()
-                .content_type("application/json")
-                .body(api_json.to_string())
+    #[actix_web::test]
+    async fn page_points_at_the_matched_scope() {
+        let scalar = Scalar::new(serde_json::json!({})).with_url("/scalar");
+        let app = App::new().service(web::scope("/{tenant}").service(scalar));
+        let app = test::init_service(app).await;
+        for tenant in ["acme", "globex"] {
+            let request = test::TestRequest::get()
+                .uri(&format!("/{tenant}/scalar"))
+                .to_request();
+            let body = test::call_and_read_body(&app, request).await;
+            let body = String::from_utf8(body.to_vec()).unwrap();
+            assert!(body.contains(&format!("/{tenant}/scalar/api-docs/openapi.json")));
+            assert!(!body.contains("{tenant}"));
         }
+    }
 
-        Resource::new(scalar_url)
-            .guard(Get())
-            .app_data(Data::new(markup.0))
-            .to(serve_scalar)
-            .register(config);
-
-        Resource::new(script_url)
-            .guard(Get())
-            .app_data(Data::new(SCALAR_API_REFERENCE_JS))
-            .to(serve_scalar_api_js)
-            .register(config);
-
-        Resource::new(api_json_url)
-            .guard(Get())
-            .app_data(Data::new(api_json))
-            .to(serve_scalar_api_json)
-            .register(config);
+    #[actix_web::test]
+    async fn tenants_see_the_original_path() {
+        let scalar = Scalar::new(serde_json::json!({}))
+            .with_url("/scalar")
+            .with_tenants(PathTenants);
+        let app = App::new().service(web::scope("/{tenant}").service(scalar));
+        let app = test::init_service(app).await;
+        let request = test::TestRequest::get()
+            .uri("/acme/scalar/api-docs/openapi.json")
+            .to_request();
+        let body = test::call_and_read_body(&app, request).await;
+        assert_eq!(body, r#"{"tenant":"acme"}"#);
+        // The 3.0 document is only known once the tenant is, its route is registered anyway
+        let request = test::TestRequest::get()
+            .uri("/acme/scalar/api-docs/openapi-3.0.json")
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let request = test::TestRequest::get()
+            .uri("/globex/scalar/api-docs/openapi.json")
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 }
@@ -1,49 +1,55 @@
-use crate::Serialize;
-use crate::{SCALAR_API_REFERENCE_JS, Scalar};
-use axum::response::IntoResponse;
-use axum::{Router, routing};
-use http::{HeaderValue, header};
+use axum::Router;
+
+use crate::{Scalar, Serialize};
 
 impl<S: Serialize, R> From<Scalar<S>> for Router<R>
 where
     R: Clone + Send + Sync + 'static,
 {
     fn from(scalar: Scalar<S>) -> Router<R> {
-        let markup = scalar.markup();
-        let scalar_url = scalar.url.as_ref();
-        let script_url = scalar.script_url();
-        let api_json = scalar.api_json();
-        let api_json_url = scalar.api_json_url();
-        Router::<R>::new()
-            .route(
-                scalar_url,
-                routing::get(move || async {
-                    let headers = [(
-                        header::CONTENT_TYPE,
-                        HeaderValue::from_static("text/html; charset=utf-8"),
-                    )];
-                    (headers, markup.0).into_response()
-                }),
-            )
-            .route(
-                script_url.as_str(),
-                routing::get(move || async {
-                    let headers = [(
-                        header::CONTENT_TYPE,
-                        HeaderValue::from_static("application/javascript"),
-                    )];
-                    (headers, SCALAR_API_REFERENCE_JS).into_response()
-                }),
-            )
-            .route(
-                api_json_url.as_str(),
-                routing::get(move || async {
-                    let headers = [(
-                        header::CONTENT_TYPE,
-                        HeaderValue::from_static("application/json"),
-                    )];
-                    (headers, api_json).into_response()
-                }),
-            )
+        let service = scalar.into_service();
+        service
+            .paths()
+            .into_iter()
+            .fold(Router::<R>::new(), |router, path| {
+                router.route_service(&path, service.clone())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use http::{Request, StatusCode};
+    use tower_service::Service;
+
+    use super::*;
+
+    async fn status(router: &mut Router, uri: &str) -> StatusCode {
+        let request = Request::get(uri).body(Body::empty()).unwrap();
+        router.call(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn nested_service_sees_paths_below_its_mount_point() {
+        let service = Scalar::new(serde_json::json!({}))
+            .with_url("/scalar")
+            .into_service();
+        let mut router = Router::new().nest_service("/scalar", service);
+        assert_eq!(status(&mut router, "/scalar").await, StatusCode::OK);
+        let spec = "/scalar/api-docs/openapi.json";
+        assert_eq!(status(&mut router, spec).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn fallback_service_only_answers_the_docs_routes() {
+        let service = Scalar::new(serde_json::json!({}))
+            .with_url("/scalar")
+            .into_service();
+        let mut router = Router::new().fallback_service(service);
+        assert_eq!(status(&mut router, "/scalar").await, StatusCode::OK);
+        assert_eq!(status(&mut router, "/").await, StatusCode::NOT_FOUND);
+        let spec = "/api-docs/openapi.json";
+        assert_eq!(status(&mut router, spec).await, StatusCode::NOT_FOUND);
     }
 }
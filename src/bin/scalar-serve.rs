@@ -0,0 +1,273 @@
+use std::ffi::OsStr;
+use std::fs;
+use std::io::Error;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::{Arc, RwLock};
+
+use axum::Router;
+use axum::extract::{Request, State};
+use axum::response::{Html, IntoResponse, Response};
+use clap::Parser;
+use maud::{DOCTYPE, html};
+use notify::{RecursiveMode, Watcher};
+use serde_json::Value;
+use tokio::net::TcpListener;
+use tower::ServiceExt;
+use utoipa_scalar_warpper::{Config, Scalar};
+
+const SPEC_EXTENSIONS: [&str; 3] = ["json", "yaml", "yml"];
+
+#[derive(Parser, Debug, Clone)]
+#[command(version, about = "Serve OpenAPI documents with the embedded Scalar UI.", long_about = None)]
+struct Cli {
+    #[arg(
+        required = true,
+        help = "OpenAPI files (json or yaml), or directories containing them"
+    )]
+    paths: Vec<PathBuf>,
+    #[arg(long, default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST), help = "Address to listen on")]
+    host: IpAddr,
+    #[arg(short, long, default_value_t = 8080, help = "Port to listen on")]
+    port: u16,
+    #[arg(long, default_value = "saturn", help = "Scalar theme")]
+    theme: String,
+    #[arg(
+        long,
+        default_value = "Scalar",
+        help = "Title of the documentation page"
+    )]
+    title: String,
+    #[arg(short, long, help = "Reload the documents when they change on disk")]
+    watch: bool,
+}
+
+type SharedRouter = Arc<RwLock<Router>>;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let cli = Cli::parse();
+    let router = build_router(&cli)?;
+    let shared = Arc::new(RwLock::new(router));
+    if cli.watch {
+        watch(cli.clone(), shared.clone())?;
+    }
+
+    let app = Router::new().fallback(serve).with_state(shared);
+    let address = SocketAddr::from((cli.host, cli.port));
+    let listener = TcpListener::bind(&address).await?;
+    println!("Serving Scalar on http://{address}");
+    axum::serve(listener, app).await
+}
+
+// The router is swapped as a whole when the documents are reloaded, so every request is
+// dispatched to whatever is current at that moment
+async fn serve(State(shared): State<SharedRouter>, request: Request) -> Response {
+    let router = shared.read().unwrap().clone();
+    router.oneshot(request).await.into_response()
+}
+
+fn build_router(cli: &Cli) -> Result<Router, Error> {
+    let specs = collect_specs(&cli.paths)?;
+    let config = Config::default().theme(&cli.theme);
+    if let [spec] = specs.as_slice() {
+        let openapi = read_spec(spec)?;
+        let scalar = Scalar::new(openapi)
+            .with_title(cli.title.clone())
+            .with_config(config);
+        return Ok(Router::from(scalar));
+    }
+
+    let mut router = Router::new();
+    let names = unique_names(&specs);
+    for (spec, name) in specs.iter().zip(&names) {
+        let openapi = read_spec(spec)?;
+        let scalar = Scalar::new(openapi)
+            .with_url(format!("/{name}"))
+            .with_title(format!("{} - {name}", cli.title))
+            .with_config(config.clone());
+        router = router.merge(scalar);
+    }
+    let index = html! {
+        (DOCTYPE)
+        head {
+            title { (cli.title) }
+            meta charset="utf-8";
+        }
+        body {
+            h1 { (cli.title) }
+            ul {
+                @for name in &names {
+                    li { a href=(format!("/{name}")) { (name) } }
+                }
+            }
+        }
+    };
+    Ok(router.route("/", axum::routing::get(move || async { Html(index.0) })))
+}
+
+// Every document is served below its file stem, documents sharing one are told apart by their
+// extension first and by a counter after that, merging two routers on the same path would panic
+fn unique_names(specs: &[PathBuf]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::with_capacity(specs.len());
+    for spec in specs {
+        let stem = spec
+            .file_stem()
+            .and_then(OsStr::to_str)
+            .map(route_segment)
+            .filter(|stem| !stem.is_empty())
+            .unwrap_or_else(|| "openapi".to_owned());
+        let stem = stem.as_str();
+        let extension = spec.extension().and_then(OsStr::to_str).map(route_segment);
+        let base = match extension {
+            Some(extension) if names.iter().any(|name| name == stem) => {
+                format!("{stem}-{extension}")
+            }
+            _ => stem.to_owned(),
+        };
+        let mut name = base.clone();
+        let mut counter = 2;
+        while names.contains(&name) {
+            name = format!("{base}-{counter}");
+            counter += 1;
+        }
+        names.push(name);
+    }
+    names
+}
+
+// axum reads braces as parameters and a leading colon or star as captures, so only characters
+// that stand for themselves in a route and a url are kept
+fn route_segment(name: &str) -> String {
+    name.chars()
+        .map(|char| match char {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' => char,
+            _ => '-',
+        })
+        .collect()
+}
+
+fn collect_specs(paths: &[PathBuf]) -> Result<Vec<PathBuf>, Error> {
+    let mut specs = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut entries = fs::read_dir(path)?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_file() && is_spec(path))
+                .collect::<Vec<_>>();
+            entries.sort();
+            specs.extend(entries);
+        } else {
+            specs.push(path.clone());
+        }
+    }
+    if specs.is_empty() {
+        return Err(Error::other("No OpenAPI documents were found"));
+    }
+    Ok(specs)
+}
+
+fn is_spec(path: &Path) -> bool {
+    path.extension()
+        .and_then(OsStr::to_str)
+        .is_some_and(|extension| SPEC_EXTENSIONS.contains(&extension))
+}
+
+fn read_spec(path: &Path) -> Result<Value, Error> {
+    let content = fs::read_to_string(path)?;
+    let is_yaml = path
+        .extension()
+        .and_then(OsStr::to_str)
+        .is_some_and(|extension| extension == "yaml" || extension == "yml");
+    let spec = if is_yaml {
+        serde_yaml::from_str(&content).map_err(|err| err.to_string())
+    } else {
+        serde_json::from_str(&content).map_err(|err| err.to_string())
+    };
+    spec.map_err(|err| Error::other(format!("Failed to parse {}: {err}", path.display())))
+}
+
+// The watches are set up before serving, so a path that cannot be watched fails the start
+fn watch(cli: Cli, shared: SharedRouter) -> Result<(), Error> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)
+        .map_err(|err| Error::other(format!("Failed to create the watcher: {err}")))?;
+    for path in &cli.paths {
+        // Editors often replace a file instead of writing it in place, which drops a watch on
+        // the file itself, so the directory holding it is watched instead
+        let dir = match path.parent() {
+            Some(parent) if !path.is_dir() && !parent.as_os_str().is_empty() => parent,
+            Some(_) if !path.is_dir() => Path::new("."),
+            _ => path,
+        };
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|err| Error::other(format!("Failed to watch {}: {err}", dir.display())))?;
+    }
+    std::thread::spawn(move || {
+        // Dropping the watcher would stop the events, it lives as long as the thread
+        let _watcher = watcher;
+        for event in rx {
+            let event = match event {
+                Ok(event) => event,
+                Err(err) => {
+                    eprintln!("Failed to watch the OpenAPI documents: {err}");
+                    continue;
+                }
+            };
+            let changed =
+                event.kind.is_modify() || event.kind.is_create() || event.kind.is_remove();
+            if !changed || !event.paths.iter().any(|path| is_spec(path)) {
+                continue;
+            }
+            match build_router(&cli) {
+                Ok(router) => {
+                    *shared.write().unwrap() = router;
+                    println!("Reloaded the OpenAPI documents");
+                }
+                // Keep serving the previous documents until the broken one is fixed
+                Err(err) => eprintln!("{err}"),
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(specs: &[&str]) -> Vec<String> {
+        let specs = specs.iter().map(PathBuf::from).collect::<Vec<_>>();
+        unique_names(&specs)
+    }
+
+    #[test]
+    fn names_are_unique() {
+        assert_eq!(
+            names(&["a/pets.json", "b/pets.json", "c/pets.yaml", "d/pets.json"]),
+            ["pets", "pets-json", "pets-yaml", "pets-json-2"]
+        );
+    }
+
+    #[test]
+    fn names_are_plain_route_segments() {
+        assert_eq!(
+            names(&[
+                "{id}.json",
+                ":tenant.yaml",
+                "*rest.yml",
+                "my api.json",
+                "{}.json"
+            ]),
+            ["-id-", "-tenant", "-rest", "my-api", "--"]
+        );
+        // Every name can be routed, which would panic on a parameter or a capture
+        let specs = ["{id}.json", ":tenant.yaml", "*rest.yml"].map(PathBuf::from);
+        let mut router = Router::<()>::new();
+        for name in unique_names(&specs) {
+            router = router.route(&format!("/{name}"), axum::routing::get(|| async {}));
+        }
+    }
+}
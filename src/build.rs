@@ -0,0 +1,167 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::service::etag;
+use crate::{Scalar, Serialize};
+
+const PREBUILT_SPEC: &str = "scalar-openapi.json";
+const PREBUILT_PAGE: &str = "scalar-index.html";
+const PREBUILT_RS: &str = "scalar-prebuilt.rs";
+
+// Everything `emit_spec` worked out at compile time, the strings live in the binary
+#[derive(Debug, Clone, Copy)]
+pub struct Prebuilt {
+    pub(crate) url: &'static str,
+    pub(crate) title: &'static str,
+    pub(crate) config: &'static str,
+    pub(crate) markup: &'static str,
+    pub(crate) markup_etag: &'static str,
+    pub(crate) api_json: &'static str,
+    pub(crate) api_json_etag: &'static str,
+}
+
+impl Prebuilt {
+    pub const fn new(
+        url: &'static str,
+        title: &'static str,
+        config: &'static str,
+        markup: (&'static str, &'static str),
+        api_json: (&'static str, &'static str),
+    ) -> Self {
+        Self {
+            url,
+            title,
+            config,
+            markup: markup.0,
+            markup_etag: markup.1,
+            api_json: api_json.0,
+            api_json_etag: api_json.1,
+        }
+    }
+}
+
+// Meant for build scripts, the returned file is brought in with
+// `Scalar::from_prebuilt(include!(concat!(env!("OUT_DIR"), "/scalar-prebuilt.rs")))`
+pub fn emit_spec<S: Serialize>(
+    scalar: &Scalar<S>,
+    out_dir: impl AsRef<Path>,
+) -> io::Result<PathBuf> {
+    // Versions and tenants are only put together when served, there is no single document to emit
+    if scalar.versions.is_some() || scalar.tenants.is_some() {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "versioned or per tenant documentation cannot be prebuilt",
+        ));
+    }
+    let out_dir = out_dir.as_ref();
+    fs::create_dir_all(out_dir)?;
+    let api_json = scalar.api_json();
+    let markup = scalar
+        .render(&scalar.api_json_url(), &scalar.script_url())
+        .0;
+    let spec_path = out_dir.join(PREBUILT_SPEC);
+    let page_path = out_dir.join(PREBUILT_PAGE);
+    fs::write(&spec_path, &api_json)?;
+    fs::write(&page_path, &markup)?;
+    // `include_str!` resolves relative paths against the including file, absolute ones work
+    // wherever the generated code ends up
+    let spec_path = fs::canonicalize(spec_path)?;
+    let page_path = fs::canonicalize(page_path)?;
+    let code = format!(
+        "::utoipa_scalar_warpper::build::Prebuilt::new(\n    {url:?},\n    {title:?},\n    {config:?},\n    (include_str!({page_path:?}), {markup_etag:?}),\n    (include_str!({spec_path:?}), {api_json_etag:?}),\n)\n",
+        url = scalar.url,
+        title = scalar.title,
+        config = scalar.config_json(),
+        markup_etag = etag(markup.as_bytes()).to_str().unwrap(),
+        api_json_etag = etag(api_json.as_bytes()).to_str().unwrap(),
+    );
+    let rs_path = out_dir.join(PREBUILT_RS);
+    fs::write(&rs_path, code)?;
+    Ok(rs_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{Request, header};
+    use serde_json::json;
+
+    use super::*;
+
+    // What `include!` makes of the emitted code, the strings are read back from the emitted files
+    fn include(code: &str, scalar: &Scalar<serde_json::Value>, out_dir: &Path) -> Prebuilt {
+        let read = |name: &str| -> &'static str {
+            let path = fs::canonicalize(out_dir.join(name)).unwrap();
+            assert!(code.contains(&format!("include_str!({path:?})")));
+            fs::read_to_string(path).unwrap().leak()
+        };
+        let markup = read(PREBUILT_PAGE);
+        let api_json = read(PREBUILT_SPEC);
+        let etag = |body: &str| -> &'static str {
+            let etag = etag(body.as_bytes()).to_str().unwrap().to_owned();
+            assert!(code.contains(&format!("{etag:?}")));
+            etag.leak()
+        };
+        let config: &'static str = scalar.config_json().leak();
+        assert!(code.contains(&format!("{config:?}")));
+        let title: &'static str = scalar.title.to_string().leak();
+        assert!(code.contains(&format!("{:?},\n    {title:?}", scalar.url)));
+        Prebuilt::new(
+            "/scalar",
+            title,
+            config,
+            (markup, etag(markup)),
+            (api_json, etag(api_json)),
+        )
+    }
+
+    #[test]
+    fn prebuilt_pages_are_served_like_rendered_ones() {
+        let out_dir = tempfile::tempdir().unwrap();
+        let scalar = Scalar::new(json!({ "openapi": "3.1.0", "paths": {} }))
+            .with_url("/scalar")
+            .with_title("Items");
+        let rs_path = emit_spec(&scalar, out_dir.path()).unwrap();
+        let code = fs::read_to_string(rs_path).unwrap();
+        assert!(code.starts_with("::utoipa_scalar_warpper::build::Prebuilt::new("));
+        let prebuilt = Scalar::from_prebuilt(include(&code, &scalar, out_dir.path()));
+
+        let rendered = scalar.clone().into_service();
+        let served = prebuilt.clone().with_openapi_30().into_service();
+        for path in ["/scalar", "/scalar/api-docs/openapi.json"] {
+            let request = Request::get(path).body(()).unwrap();
+            let expected = rendered.handle(&request).unwrap();
+            let response = served.handle(&request).unwrap();
+            assert_eq!(response.body(), expected.body());
+            assert_eq!(
+                response.headers()[header::ETAG],
+                expected.headers()[header::ETAG]
+            );
+        }
+        let request = Request::get("/scalar/api-docs/openapi-3.0.json")
+            .body(())
+            .unwrap();
+        let converted = served.handle(&request).unwrap();
+        assert_eq!(converted.body(), r#"{"openapi":"3.0.3","paths":{}}"#);
+
+        // The prebuilt document is a document like any other
+        let export = tempfile::tempdir().unwrap();
+        prebuilt.export_to_dir(export.path()).unwrap();
+        let exported = fs::read_to_string(export.path().join("api-docs/openapi.json")).unwrap();
+        assert_eq!(exported, r#"{"openapi":"3.1.0","paths":{}}"#);
+    }
+
+    #[test]
+    #[should_panic(expected = "the prebuilt Scalar config is malformed")]
+    fn malformed_prebuilt_config_is_reported() {
+        let prebuilt = Prebuilt::new("/", "Scalar", "{", ("", ""), ("{}", ""));
+        Scalar::from_prebuilt(prebuilt);
+    }
+
+    #[test]
+    fn versioned_documentation_is_not_prebuilt() {
+        let out_dir = tempfile::tempdir().unwrap();
+        let error = emit_spec(&Scalar::versioned(), out_dir.path()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -0,0 +1,333 @@
+use std::collections::BTreeMap;
+
+use maud::{DOCTYPE, Markup, html};
+use utoipa::openapi::OpenApi;
+use utoipa::openapi::path::Operation;
+
+use crate::diff::{Change, diff};
+use crate::spec::path_operations;
+use crate::{Scalar, Serialize};
+
+const BREAKING_STYLE: &str = "color: #b71c1c; font-weight: bold;";
+
+#[derive(Debug, Clone)]
+pub(crate) struct Changelog {
+    // Newest first, the way the page lists them
+    releases: Vec<Release>,
+}
+
+#[derive(Debug, Clone)]
+struct Release {
+    version: String,
+    previous: Option<String>,
+    added: Vec<Entry>,
+    removed: Vec<Entry>,
+    changed: Vec<Entry>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    operation: String,
+    anchor: Option<String>,
+    details: Vec<Change>,
+}
+
+impl Changelog {
+    fn new<V: AsRef<str>>(history: &[(V, OpenApi)]) -> Self {
+        // Only operations of the latest version are on the page, so only those can be linked
+        let anchors = history
+            .last()
+            .map(|(_, openapi)| {
+                operations(openapi)
+                    .into_iter()
+                    .map(|(key, operation)| {
+                        let anchor = anchor(&key, operation);
+                        (key, anchor)
+                    })
+                    .collect::<BTreeMap<_, _>>()
+            })
+            .unwrap_or_default();
+        let entry = |operation: &str, details: Vec<Change>| Entry {
+            operation: operation.to_owned(),
+            anchor: anchors.get(operation).cloned(),
+            details,
+        };
+        let mut releases = Vec::with_capacity(history.len());
+        let mut previous: Option<&(V, OpenApi)> = None;
+        for current @ (version, openapi) in history {
+            let mut release = Release {
+                version: version.as_ref().to_owned(),
+                previous: previous.map(|(version, _)| version.as_ref().to_owned()),
+                added: Vec::new(),
+                removed: Vec::new(),
+                changed: Vec::new(),
+            };
+            if let Some((_, old)) = previous {
+                let report = diff(old, openapi);
+                let old_operations = operations(old);
+                let new_operations = operations(openapi);
+                for key in old_operations.keys() {
+                    if !new_operations.contains_key(key) {
+                        release.removed.push(entry(key, Vec::new()));
+                    }
+                }
+                for (key, operation) in &new_operations {
+                    let Some(old_operation) = old_operations.get(key) else {
+                        release.added.push(entry(key, Vec::new()));
+                        continue;
+                    };
+                    let scope = format!("{key} ");
+                    let details = report
+                        .changes()
+                        .iter()
+                        .filter(|change| {
+                            change.location() == key || change.location().starts_with(&scope)
+                        })
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    // Edits to descriptions and examples are not in the report, they still count
+                    let edited = serde_json::to_value(old_operation).ok()
+                        != serde_json::to_value(operation).ok();
+                    if edited || !details.is_empty() {
+                        release.changed.push(entry(key, details));
+                    }
+                }
+            }
+            releases.push(release);
+            previous = Some(current);
+        }
+        releases.reverse();
+        Self { releases }
+    }
+
+    pub(crate) fn render(&self, title: &str, docs_url: &str) -> Markup {
+        html! {
+            (DOCTYPE)
+            head {
+                title { (title) " - Changelog" }
+                meta charset="utf-8";
+                meta name="viewport" content="width=device-width, initial-scale=1.0";
+            }
+            body style="max-width: 960px; margin: 0 auto; padding: 16px; font: 15px sans-serif;" {
+                h1 { (title) " changelog" }
+                p { a href=(docs_url) { "Back to the documentation" } }
+                @for release in &self.releases {
+                    section {
+                        h2 { (release.version) }
+                        @match &release.previous {
+                            Some(previous) => {
+                                p { "Changes since " (previous) "." }
+                                (section("Added", &release.added, docs_url))
+                                (section("Removed", &release.removed, docs_url))
+                                (section("Changed", &release.changed, docs_url))
+                                @if release.added.is_empty()
+                                    && release.removed.is_empty()
+                                    && release.changed.is_empty() {
+                                    p { "No changes to the operations." }
+                                }
+                            }
+                            None => p { "Initial version." },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn section(heading: &str, entries: &[Entry], docs_url: &str) -> Markup {
+    html! {
+        @if !entries.is_empty() {
+            h3 { (heading) }
+            ul {
+                @for entry in entries {
+                    li {
+                        @match &entry.anchor {
+                            Some(anchor) => a href={ (docs_url) "#" (anchor) } {
+                                code { (entry.operation) }
+                            },
+                            None => code { (entry.operation) },
+                        }
+                        @if !entry.details.is_empty() {
+                            ul {
+                                @for change in &entry.details {
+                                    li {
+                                        @if change.is_breaking() {
+                                            span style=(BREAKING_STYLE) { "Breaking: " }
+                                        }
+                                        (change.message())
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn operations(openapi: &OpenApi) -> BTreeMap<String, &Operation> {
+    openapi
+        .paths
+        .paths
+        .iter()
+        .flat_map(|(path, item)| {
+            path_operations(item).map(move |(method, operation)| {
+                (format!("{} {path}", method.to_uppercase()), operation)
+            })
+        })
+        .collect()
+}
+
+// Scalar addresses an operation by its first tag, the method and the path
+fn anchor(key: &str, operation: &Operation) -> String {
+    let tag = operation
+        .tags
+        .as_ref()
+        .and_then(|tags| tags.first())
+        .map(|tag| {
+            tag.to_lowercase()
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join("-")
+        })
+        .unwrap_or_else(|| "default".to_owned());
+    let (method, path) = key.split_once(' ').unwrap_or((key, ""));
+    format!("tag/{tag}/{method}{path}")
+}
+
+impl<S: Serialize> Scalar<S> {
+    // The versions are expected oldest first, each one is compared against the one before it
+    pub fn with_history<V: AsRef<str>>(mut self, history: Vec<(V, OpenApi)>) -> Self {
+        self.changelog = Some(Changelog::new(&history)).filter(|_| !history.is_empty());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{Request, StatusCode, header};
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    fn document(paths: Value) -> OpenApi {
+        serde_json::from_value(json!({
+            "openapi": "3.1.0",
+            "info": { "title": "Items", "version": "1.0.0" },
+            "paths": paths
+        }))
+        .unwrap()
+    }
+
+    fn operation(tags: Value, responses: Value) -> Value {
+        json!({ "tags": tags, "responses": responses })
+    }
+
+    fn history() -> Vec<(&'static str, OpenApi)> {
+        let found = json!({ "200": { "description": "Found" } });
+        let missing = json!({
+            "200": { "description": "Found" },
+            "404": { "description": "Missing" }
+        });
+        let v1 = document(json!({
+            "/items": { "get": operation(json!(["Item Store"]), missing) },
+            "/items/{id}": { "delete": operation(json!(["items"]), found.clone()) },
+            "/users": { "get": operation(json!(["users"]), found.clone()) }
+        }));
+        let v2 = document(json!({
+            "/items": { "get": operation(json!(["Item Store"]), found.clone()) },
+            "/items/{id}": { "get": operation(Value::Null, found.clone()) },
+            "/users": { "get": operation(json!(["users"]), found) }
+        }));
+        vec![("1", v1), ("2", v2)]
+    }
+
+    fn operations(entries: &[Entry]) -> Vec<&str> {
+        entries
+            .iter()
+            .map(|entry| entry.operation.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn operations_are_sorted_by_what_happened_to_them() {
+        let changelog = Changelog::new(&history());
+        let [latest, first] = &changelog.releases[..] else {
+            panic!("expected two releases, newest first");
+        };
+        assert_eq!(latest.version, "2");
+        assert_eq!(latest.previous.as_deref(), Some("1"));
+        assert_eq!(operations(&latest.added), ["GET /items/{id}"]);
+        assert_eq!(operations(&latest.removed), ["DELETE /items/{id}"]);
+        // The unchanged `GET /users` is left out
+        assert_eq!(operations(&latest.changed), ["GET /items"]);
+        let details = &latest.changed[0].details;
+        assert_eq!(details.len(), 1);
+        assert!(details[0].is_breaking());
+
+        assert_eq!(first.version, "1");
+        assert_eq!(first.previous, None);
+        assert!(first.added.is_empty() && first.removed.is_empty() && first.changed.is_empty());
+    }
+
+    #[test]
+    fn edits_outside_the_diff_count_as_changes() {
+        let described = |description: &str| {
+            document(json!({
+                "/items": { "get": { "description": description, "responses": {} } }
+            }))
+        };
+        let changelog = Changelog::new(&[("1", described("Lists")), ("2", described("All"))]);
+        let latest = &changelog.releases[0];
+        assert_eq!(operations(&latest.changed), ["GET /items"]);
+        assert!(latest.changed[0].details.is_empty());
+    }
+
+    #[test]
+    fn anchors_follow_the_first_tag_the_method_and_the_path() {
+        let changelog = Changelog::new(&history());
+        let latest = &changelog.releases[0];
+        let anchor = |entry: &Entry| entry.anchor.clone();
+        assert_eq!(
+            anchor(&latest.changed[0]).as_deref(),
+            Some("tag/item-store/GET/items")
+        );
+        assert_eq!(
+            anchor(&latest.added[0]).as_deref(),
+            Some("tag/default/GET/items/{id}")
+        );
+        // Removed operations are not on the page any more
+        assert_eq!(anchor(&latest.removed[0]), None);
+    }
+
+    #[test]
+    fn changelog_is_served_next_to_the_page() {
+        let (_, latest) = history().pop().unwrap();
+        let service = Scalar::new(latest)
+            .with_url("/docs")
+            .with_history(history())
+            .into_service();
+        assert_eq!(service.changelog_url(), Some("/docs/changelog"));
+        let request = Request::get("/docs/changelog").body(()).unwrap();
+        let response = service.handle(&request).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/html; charset=utf-8"
+        );
+        let body = String::from_utf8(response.into_body().to_vec()).unwrap();
+        assert!(body.contains("Changes since 1."));
+        assert!(body.contains("Initial version."));
+        assert!(body.contains(r##"<a href="/docs#tag/item-store/GET/items">"##));
+        assert!(body.contains("Breaking: "));
+
+        let service = Scalar::new(document(json!({})))
+            .with_url("/docs")
+            .into_service();
+        assert_eq!(service.changelog_url(), None);
+        let request = Request::get("/docs/changelog").body(()).unwrap();
+        assert!(service.handle(&request).is_none());
+    }
+}
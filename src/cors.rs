@@ -0,0 +1,169 @@
+use http::response::Builder;
+use http::{HeaderValue, Request, header};
+
+const ALLOW_METHODS: &str = "GET, HEAD";
+
+#[derive(Debug, Clone, Default)]
+pub struct CorsPolicy {
+    any_origin: bool,
+    origins: Vec<String>,
+    max_age: Option<u64>,
+}
+
+impl CorsPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn any() -> Self {
+        Self {
+            any_origin: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn allow_origin(mut self, origin: impl AsRef<str>) -> Self {
+        let origin = origin.as_ref().trim_end_matches('/').to_owned();
+        self.origins.push(origin);
+        self
+    }
+
+    pub fn max_age(mut self, seconds: u64) -> Self {
+        self.max_age = Some(seconds);
+        self
+    }
+
+    pub(crate) fn apply(&self, request: &Request<()>, builder: Builder) -> Builder {
+        if self.any_origin {
+            return builder.header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*");
+        }
+        // The answer depends on the origin, caches must not hand it to another one
+        let builder = builder.header(header::VARY, header::ORIGIN.as_str());
+        match request.headers().get(header::ORIGIN) {
+            Some(origin) if self.allows(origin) => {
+                builder.header(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin.clone())
+            }
+            _ => builder,
+        }
+    }
+
+    fn allows(&self, origin: &HeaderValue) -> bool {
+        self.origins
+            .iter()
+            .any(|allowed| origin == allowed.as_str())
+    }
+
+    pub(crate) fn preflight(&self, request: &Request<()>, builder: Builder) -> Builder {
+        let headers = request.headers();
+        if !headers.contains_key(header::ACCESS_CONTROL_REQUEST_METHOD) {
+            return builder;
+        }
+        let mut builder = self
+            .apply(request, builder)
+            .header(header::ACCESS_CONTROL_ALLOW_METHODS, ALLOW_METHODS);
+        if let Some(requested) = headers.get(header::ACCESS_CONTROL_REQUEST_HEADERS) {
+            builder = builder.header(header::ACCESS_CONTROL_ALLOW_HEADERS, requested.clone());
+        }
+        if let Some(max_age) = self.max_age {
+            builder = builder.header(header::ACCESS_CONTROL_MAX_AGE, HeaderValue::from(max_age));
+        }
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{HeaderMap, Response};
+
+    use super::*;
+
+    fn request(headers: &[(&str, &str)]) -> Request<()> {
+        let mut builder = Request::get("/api-docs/openapi.json");
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        builder.body(()).unwrap()
+    }
+
+    fn applied(policy: &CorsPolicy, headers: &[(&str, &str)]) -> HeaderMap {
+        let builder = policy.apply(&request(headers), Response::builder());
+        builder.body(()).unwrap().headers().clone()
+    }
+
+    fn preflight(policy: &CorsPolicy, headers: &[(&str, &str)]) -> HeaderMap {
+        let builder = policy.preflight(&request(headers), Response::builder());
+        builder.body(()).unwrap().headers().clone()
+    }
+
+    #[test]
+    fn any_origin_is_answered_with_a_wildcard() {
+        let headers = applied(&CorsPolicy::any(), &[("origin", "https://a.dev")]);
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+        // The answer is the same for every origin, caches may share it
+        assert!(!headers.contains_key(header::VARY));
+    }
+
+    #[test]
+    fn allowed_origins_are_echoed() {
+        let policy = CorsPolicy::new()
+            .allow_origin("https://a.dev/")
+            .allow_origin("https://b.dev");
+        for origin in ["https://a.dev", "https://b.dev"] {
+            let headers = applied(&policy, &[("origin", origin)]);
+            assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], origin);
+            assert_eq!(headers[header::VARY], "origin");
+        }
+    }
+
+    #[test]
+    fn other_origins_are_rejected() {
+        let policy = CorsPolicy::new().allow_origin("https://a.dev");
+        for headers in [
+            &[("origin", "https://evil.dev")][..],
+            &[("origin", "https://a.dev.evil.dev")],
+            &[("origin", "http://a.dev")],
+            &[],
+        ] {
+            let headers = applied(&policy, headers);
+            assert!(!headers.contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+            // Without `Vary`, a cache could hand an allowed answer to a rejected origin
+            assert_eq!(headers[header::VARY], "origin");
+        }
+    }
+
+    #[test]
+    fn preflight_allows_the_methods_and_the_requested_headers() {
+        let policy = CorsPolicy::new().allow_origin("https://a.dev").max_age(600);
+        let headers = preflight(
+            &policy,
+            &[
+                ("origin", "https://a.dev"),
+                ("access-control-request-method", "GET"),
+                ("access-control-request-headers", "x-api-key"),
+            ],
+        );
+        assert_eq!(
+            headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://a.dev"
+        );
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_METHODS], ALLOW_METHODS);
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_HEADERS], "x-api-key");
+        assert_eq!(headers[header::ACCESS_CONTROL_MAX_AGE], "600");
+    }
+
+    #[test]
+    fn preflight_needs_a_requested_method() {
+        let policy = CorsPolicy::any().max_age(600);
+        let headers = preflight(&policy, &[("origin", "https://a.dev")]);
+        assert!(headers.is_empty());
+        let rejected = preflight(
+            &CorsPolicy::new().allow_origin("https://a.dev"),
+            &[
+                ("origin", "https://evil.dev"),
+                ("access-control-request-method", "GET"),
+            ],
+        );
+        assert!(!rejected.contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+        assert!(!rejected.contains_key(header::ACCESS_CONTROL_MAX_AGE));
+    }
+}
@@ -0,0 +1,1024 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde::Serialize as SerdeSerialize;
+use serde_json::{Map, Value};
+use utoipa::openapi::OpenApi;
+use utoipa::openapi::Required;
+use utoipa::openapi::path::{Operation, Parameter, PathItem};
+
+use crate::spec::path_operations;
+
+#[non_exhaustive]
+#[derive(SerdeSerialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeKind {
+    EndpointAdded,
+    EndpointRemoved,
+    ParameterAdded,
+    ParameterRemoved,
+    ParameterRequired,
+    ParameterOptional,
+    RequestBodyRequired,
+    RequestBodyOptional,
+    ResponseAdded,
+    ResponseRemoved,
+    SchemaAdded,
+    SchemaRemoved,
+    SchemaChanged,
+    PropertyAdded,
+    PropertyRemoved,
+    PropertyRequired,
+    EnumNarrowed,
+    EnumWidened,
+    TypeNarrowed,
+    TypeWidened,
+    ContentRemoved,
+}
+
+// Who reads a schema decides which changes break. A client can no longer rely on what a response
+// stopped promising, and can no longer send what a request stopped accepting. Components may be
+// used on either side, so both apply to them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Request,
+    Response,
+    Both,
+}
+
+impl Direction {
+    fn client_sends(self) -> bool {
+        self != Direction::Response
+    }
+
+    fn client_reads(self) -> bool {
+        self != Direction::Request
+    }
+}
+
+#[derive(SerdeSerialize, Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    kind: ChangeKind,
+    breaking: bool,
+    location: String,
+    message: String,
+}
+
+impl Change {
+    pub fn kind(&self) -> ChangeKind {
+        self.kind
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        self.breaking
+    }
+
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let impact = if self.breaking {
+            "breaking"
+        } else {
+            "non-breaking"
+        };
+        write!(f, "{impact} {}: {}", self.location, self.message)
+    }
+}
+
+#[derive(SerdeSerialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeReport {
+    changes: Vec<Change>,
+}
+
+impl ChangeReport {
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|change| change.breaking)
+    }
+
+    pub fn has_breaking(&self) -> bool {
+        self.breaking().next().is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    fn push(
+        &mut self,
+        kind: ChangeKind,
+        breaking: bool,
+        location: impl AsRef<str>,
+        message: impl AsRef<str>,
+    ) {
+        self.changes.push(Change {
+            kind,
+            breaking,
+            location: location.as_ref().to_owned(),
+            message: message.as_ref().to_owned(),
+        });
+    }
+}
+
+impl fmt::Display for ChangeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+pub fn diff(old: &OpenApi, new: &OpenApi) -> ChangeReport {
+    let mut report = ChangeReport::default();
+    diff_operations(old, new, &mut report);
+    diff_components(old, new, &mut report);
+    report
+}
+
+fn operations(openapi: &OpenApi) -> BTreeMap<String, (&PathItem, &Operation)> {
+    openapi
+        .paths
+        .paths
+        .iter()
+        .flat_map(|(path, item)| {
+            path_operations(item).map(move |(method, operation)| {
+                (
+                    format!("{} {path}", method.to_uppercase()),
+                    (item, operation),
+                )
+            })
+        })
+        .collect()
+}
+
+fn diff_operations(old: &OpenApi, new: &OpenApi, report: &mut ChangeReport) {
+    let old_operations = operations(old);
+    let new_operations = operations(new);
+    for location in old_operations.keys() {
+        if !new_operations.contains_key(location) {
+            report.push(
+                ChangeKind::EndpointRemoved,
+                true,
+                location,
+                "endpoint was removed",
+            );
+        }
+    }
+    for (location, (new_item, new_operation)) in &new_operations {
+        let Some((old_item, old_operation)) = old_operations.get(location) else {
+            report.push(
+                ChangeKind::EndpointAdded,
+                false,
+                location,
+                "endpoint was added",
+            );
+            continue;
+        };
+        diff_parameters(
+            location,
+            parameters(old_item, old_operation),
+            parameters(new_item, new_operation),
+            report,
+        );
+        diff_request_body(location, old_operation, new_operation, report);
+        diff_responses(location, old_operation, new_operation, report);
+    }
+}
+
+// Parameters of the path item apply to every operation below it, the operation can override them
+fn parameters<'a>(
+    item: &'a PathItem,
+    operation: &'a Operation,
+) -> BTreeMap<(String, String), &'a Parameter> {
+    item.parameters
+        .iter()
+        .chain(operation.parameters.iter())
+        .flatten()
+        .map(|parameter| {
+            let location = serde_json::to_value(&parameter.parameter_in).unwrap();
+            let location = location.as_str().unwrap_or_default().to_owned();
+            ((location, parameter.name.clone()), parameter)
+        })
+        .collect()
+}
+
+fn diff_parameters(
+    location: &str,
+    old: BTreeMap<(String, String), &Parameter>,
+    new: BTreeMap<(String, String), &Parameter>,
+    report: &mut ChangeReport,
+) {
+    for (key @ (parameter_in, name), old_parameter) in &old {
+        let Some(new_parameter) = new.get(key) else {
+            report.push(
+                ChangeKind::ParameterRemoved,
+                false,
+                location,
+                format!("{parameter_in} parameter `{name}` was removed"),
+            );
+            continue;
+        };
+        match (is_required(old_parameter), is_required(new_parameter)) {
+            (false, true) => report.push(
+                ChangeKind::ParameterRequired,
+                true,
+                location,
+                format!("{parameter_in} parameter `{name}` became required"),
+            ),
+            (true, false) => report.push(
+                ChangeKind::ParameterOptional,
+                false,
+                location,
+                format!("{parameter_in} parameter `{name}` became optional"),
+            ),
+            _ => {}
+        }
+        diff_schema(
+            &format!("{location} {parameter_in} parameter `{name}`"),
+            &serde_json::to_value(&old_parameter.schema).unwrap(),
+            &serde_json::to_value(&new_parameter.schema).unwrap(),
+            Direction::Request,
+            report,
+        );
+    }
+    for (key @ (parameter_in, name), new_parameter) in &new {
+        if !old.contains_key(key) {
+            let required = is_required(new_parameter);
+            let adjective = if required { "required" } else { "optional" };
+            report.push(
+                ChangeKind::ParameterAdded,
+                required,
+                location,
+                format!("{adjective} {parameter_in} parameter `{name}` was added"),
+            );
+        }
+    }
+}
+
+fn is_required(parameter: &Parameter) -> bool {
+    matches!(parameter.required, Required::True)
+}
+
+fn diff_request_body(location: &str, old: &Operation, new: &Operation, report: &mut ChangeReport) {
+    let old_required = old
+        .request_body
+        .as_ref()
+        .is_some_and(|body| matches!(body.required, Some(Required::True)));
+    let new_required = new
+        .request_body
+        .as_ref()
+        .is_some_and(|body| matches!(body.required, Some(Required::True)));
+    match (old_required, new_required) {
+        (false, true) => report.push(
+            ChangeKind::RequestBodyRequired,
+            true,
+            location,
+            "request body became required",
+        ),
+        (true, false) => report.push(
+            ChangeKind::RequestBodyOptional,
+            false,
+            location,
+            "request body became optional",
+        ),
+        _ => {}
+    }
+    let (Some(old_body), Some(new_body)) = (&old.request_body, &new.request_body) else {
+        return;
+    };
+    for (media_type, old_content) in &old_body.content {
+        match new_body.content.get(media_type) {
+            Some(new_content) => diff_schema(
+                &format!("{location} request body `{media_type}`"),
+                &serde_json::to_value(&old_content.schema).unwrap(),
+                &serde_json::to_value(&new_content.schema).unwrap(),
+                Direction::Request,
+                report,
+            ),
+            None => report.push(
+                ChangeKind::ContentRemoved,
+                true,
+                location,
+                format!("request body `{media_type}` content was removed"),
+            ),
+        }
+    }
+}
+
+fn diff_responses(location: &str, old: &Operation, new: &Operation, report: &mut ChangeReport) {
+    // Responses may be references, the serialized form compares both kinds alike
+    let old_responses = serde_json::to_value(&old.responses).unwrap();
+    let new_responses = serde_json::to_value(&new.responses).unwrap();
+    let (Some(old_responses), Some(new_responses)) =
+        (old_responses.as_object(), new_responses.as_object())
+    else {
+        return;
+    };
+    for status in old_responses.keys() {
+        if !new_responses.contains_key(status) {
+            report.push(
+                ChangeKind::ResponseRemoved,
+                true,
+                location,
+                format!("response `{status}` was removed"),
+            );
+        }
+    }
+    for (status, new_response) in new_responses {
+        let Some(old_response) = old_responses.get(status) else {
+            report.push(
+                ChangeKind::ResponseAdded,
+                false,
+                location,
+                format!("response `{status}` was added"),
+            );
+            continue;
+        };
+        let response_location = format!("{location} response `{status}`");
+        if old_response.get("$ref") != new_response.get("$ref") {
+            report.push(
+                ChangeKind::SchemaChanged,
+                true,
+                &response_location,
+                "response reference changed",
+            );
+            continue;
+        }
+        // A response without content has lost every media type it had
+        let empty = Map::new();
+        let old_content = old_response.get("content").and_then(Value::as_object);
+        let new_content = new_response.get("content").and_then(Value::as_object);
+        let new_content = new_content.unwrap_or(&empty);
+        for (media_type, old_media) in old_content.unwrap_or(&empty) {
+            match new_content.get(media_type) {
+                Some(new_media) => diff_schema(
+                    &format!("{response_location} `{media_type}`"),
+                    old_media.get("schema").unwrap_or(&Value::Null),
+                    new_media.get("schema").unwrap_or(&Value::Null),
+                    Direction::Response,
+                    report,
+                ),
+                None => report.push(
+                    ChangeKind::ContentRemoved,
+                    true,
+                    &response_location,
+                    format!("`{media_type}` content was removed"),
+                ),
+            }
+        }
+    }
+}
+
+fn diff_components(old: &OpenApi, new: &OpenApi, report: &mut ChangeReport) {
+    let old_schemas = old
+        .components
+        .as_ref()
+        .map(|components| &components.schemas);
+    let new_schemas = new
+        .components
+        .as_ref()
+        .map(|components| &components.schemas);
+    let empty = BTreeMap::new();
+    let old_schemas = old_schemas.unwrap_or(&empty);
+    let new_schemas = new_schemas.unwrap_or(&empty);
+    for (name, old_schema) in old_schemas {
+        let location = format!("#/components/schemas/{name}");
+        match new_schemas.get(name) {
+            Some(new_schema) => diff_schema(
+                &location,
+                &serde_json::to_value(old_schema).unwrap(),
+                &serde_json::to_value(new_schema).unwrap(),
+                Direction::Both,
+                report,
+            ),
+            None => report.push(
+                ChangeKind::SchemaRemoved,
+                true,
+                &location,
+                "schema was removed",
+            ),
+        }
+    }
+    for name in new_schemas.keys() {
+        if !old_schemas.contains_key(name) {
+            report.push(
+                ChangeKind::SchemaAdded,
+                false,
+                format!("#/components/schemas/{name}"),
+                "schema was added",
+            );
+        }
+    }
+}
+
+// Schemas are compared in their serialized form, which covers the 3.0 and 3.1 keywords alike.
+// Named schemas are compared once under components, a reference only has to keep its target
+fn diff_schema(
+    location: &str,
+    old: &Value,
+    new: &Value,
+    direction: Direction,
+    report: &mut ChangeReport,
+) {
+    if old.get("$ref") != new.get("$ref") {
+        report.push(
+            ChangeKind::SchemaChanged,
+            true,
+            location,
+            "schema reference changed",
+        );
+        return;
+    }
+    let old_types = types(old);
+    let new_types = types(new);
+    if old_types != new_types {
+        let describe = |types: &Option<BTreeSet<String>>| match types {
+            Some(types) => types.iter().cloned().collect::<Vec<_>>().join(", "),
+            None => "any".to_owned(),
+        };
+        let change = format!(
+            "from `{}` to `{}`",
+            describe(&old_types),
+            describe(&new_types)
+        );
+        if accepts_all(&new_types, &old_types) {
+            report.push(
+                ChangeKind::TypeWidened,
+                direction.client_reads(),
+                location,
+                format!("type widened {change}"),
+            );
+        } else if accepts_all(&old_types, &new_types) {
+            report.push(
+                ChangeKind::TypeNarrowed,
+                direction.client_sends(),
+                location,
+                format!("type narrowed {change}"),
+            );
+        } else {
+            report.push(
+                ChangeKind::SchemaChanged,
+                true,
+                location,
+                format!("type changed {change}"),
+            );
+            return;
+        }
+    }
+    let old_enum = string_set(old.get("enum"));
+    let new_enum = string_set(new.get("enum"));
+    if !old_enum.is_empty() || !new_enum.is_empty() {
+        let removed = old_enum.difference(&new_enum).cloned().collect::<Vec<_>>();
+        let added = new_enum.difference(&old_enum).cloned().collect::<Vec<_>>();
+        if !removed.is_empty() {
+            report.push(
+                ChangeKind::EnumNarrowed,
+                direction.client_sends(),
+                location,
+                format!("enum values removed: {}", removed.join(", ")),
+            );
+        }
+        if !added.is_empty() {
+            report.push(
+                ChangeKind::EnumWidened,
+                direction.client_reads(),
+                location,
+                format!("enum values added: {}", added.join(", ")),
+            );
+        }
+    }
+    let old_required = string_set(old.get("required"));
+    for name in string_set(new.get("required")).difference(&old_required) {
+        report.push(
+            ChangeKind::PropertyRequired,
+            direction.client_sends(),
+            location,
+            format!("property `{name}` became required"),
+        );
+    }
+    let old_properties = old.get("properties").and_then(Value::as_object);
+    let new_properties = new.get("properties").and_then(Value::as_object);
+    if let (Some(old_properties), Some(new_properties)) = (old_properties, new_properties) {
+        for (name, old_property) in old_properties {
+            match new_properties.get(name) {
+                Some(new_property) => diff_schema(
+                    &format!("{location}.{name}"),
+                    old_property,
+                    new_property,
+                    direction,
+                    report,
+                ),
+                None => report.push(
+                    ChangeKind::PropertyRemoved,
+                    direction.client_reads(),
+                    location,
+                    format!("property `{name}` was removed"),
+                ),
+            }
+        }
+        for name in new_properties.keys() {
+            if !old_properties.contains_key(name) {
+                report.push(
+                    ChangeKind::PropertyAdded,
+                    false,
+                    location,
+                    format!("property `{name}` was added"),
+                );
+            }
+        }
+    }
+    if let (Some(old_items), Some(new_items)) = (old.get("items"), new.get("items")) {
+        diff_schema(
+            &format!("{location}[]"),
+            old_items,
+            new_items,
+            direction,
+            report,
+        );
+    }
+    for keyword in ["oneOf", "anyOf", "allOf"] {
+        diff_variants(location, keyword, old, new, direction, report);
+    }
+}
+
+// `Option<T>` of a named schema is a `oneOf` of `null` and the reference, so the variants carry
+// the change. Variants are paired by their reference, the rest by their order
+fn diff_variants(
+    location: &str,
+    keyword: &str,
+    old: &Value,
+    new: &Value,
+    direction: Direction,
+    report: &mut ChangeReport,
+) {
+    let empty = Vec::new();
+    let old_variants = old.get(keyword).and_then(Value::as_array);
+    let new_variants = new.get(keyword).and_then(Value::as_array);
+    let old_variants = old_variants.unwrap_or(&empty);
+    let new_variants = new_variants.unwrap_or(&empty);
+    let mut pairs = Vec::new();
+    let mut old_left = Vec::new();
+    let mut new_left = (0..new_variants.len()).collect::<Vec<_>>();
+    for (index, variant) in old_variants.iter().enumerate() {
+        let reference = variant.get("$ref");
+        let matched = new_left
+            .iter()
+            .position(|new| reference.is_some() && new_variants[*new].get("$ref") == reference);
+        match matched {
+            Some(position) => pairs.push((index, new_left.remove(position))),
+            None => old_left.push(index),
+        }
+    }
+    let paired = old_left.len().min(new_left.len());
+    pairs.extend(old_left.drain(..paired).zip(new_left.drain(..paired)));
+    pairs.sort_unstable();
+    for (old_index, new_index) in pairs {
+        diff_schema(
+            &format!("{location}.{keyword}[{old_index}]"),
+            &old_variants[old_index],
+            &new_variants[new_index],
+            direction,
+            report,
+        );
+    }
+    // Every `allOf` variant constrains the value further, the others each allow one more shape
+    let narrows = |added: bool| added == (keyword == "allOf");
+    let changed = old_left
+        .into_iter()
+        .map(|index| (index, false))
+        .chain(new_left.into_iter().map(|index| (index, true)));
+    for (index, added) in changed {
+        let action = if added { "added" } else { "removed" };
+        let message = format!("`{keyword}` variant {index} was {action}");
+        if narrows(added) {
+            report.push(
+                ChangeKind::TypeNarrowed,
+                direction.client_sends(),
+                location,
+                message,
+            );
+        } else {
+            report.push(
+                ChangeKind::TypeWidened,
+                direction.client_reads(),
+                location,
+                message,
+            );
+        }
+    }
+}
+
+// `None` when any type is allowed. The 3.0 `nullable` is the 3.1 `null` type
+fn types(schema: &Value) -> Option<BTreeSet<String>> {
+    let mut types = match schema.get("type")? {
+        Value::String(kind) => BTreeSet::from([kind.clone()]),
+        types => string_set(Some(types)),
+    };
+    if schema.get("nullable") == Some(&Value::Bool(true)) {
+        types.insert("null".to_owned());
+    }
+    Some(types)
+}
+
+// Whether every value of the `narrow` types is also one of the `wide` types
+fn accepts_all(wide: &Option<BTreeSet<String>>, narrow: &Option<BTreeSet<String>>) -> bool {
+    match (wide, narrow) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(wide), Some(narrow)) => narrow
+            .iter()
+            .all(|kind| wide.contains(kind) || kind == "integer" && wide.contains("number")),
+    }
+}
+
+fn string_set(value: Option<&Value>) -> BTreeSet<String> {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|value| match value {
+            Value::String(value) => value.clone(),
+            value => value.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn document(paths: Value, schemas: Value) -> OpenApi {
+        serde_json::from_value(json!({
+            "openapi": "3.1.0",
+            "info": { "title": "items", "version": "1" },
+            "paths": paths,
+            "components": { "schemas": schemas }
+        }))
+        .unwrap()
+    }
+
+    fn get(operation: Value) -> Value {
+        json!({ "/items": { "get": operation } })
+    }
+
+    fn post_body(schema: Value) -> Value {
+        json!({
+            "/items": {
+                "post": {
+                    "requestBody": { "content": { "application/json": { "schema": schema } } },
+                    "responses": {}
+                }
+            }
+        })
+    }
+
+    fn ok_response(schema: Value) -> Value {
+        get(json!({
+            "responses": {
+                "200": {
+                    "description": "items",
+                    "content": { "application/json": { "schema": schema } }
+                }
+            }
+        }))
+    }
+
+    fn query(required: bool) -> Value {
+        get(json!({
+            "parameters": [{ "name": "page", "in": "query", "required": required }],
+            "responses": {}
+        }))
+    }
+
+    fn changes(old: (Value, Value), new: (Value, Value)) -> Vec<(ChangeKind, bool)> {
+        let report = diff(&document(old.0, old.1), &document(new.0, new.1));
+        report
+            .changes()
+            .iter()
+            .map(|change| (change.kind(), change.is_breaking()))
+            .collect()
+    }
+
+    fn paths(paths: Value) -> (Value, Value) {
+        (paths, json!({}))
+    }
+
+    fn schemas(schemas: Value) -> (Value, Value) {
+        (json!({}), schemas)
+    }
+
+    #[test]
+    fn endpoints() {
+        let listed = paths(get(json!({ "responses": {} })));
+        assert_eq!(
+            changes(paths(json!({})), listed.clone()),
+            [(ChangeKind::EndpointAdded, false)]
+        );
+        assert_eq!(
+            changes(listed, paths(json!({}))),
+            [(ChangeKind::EndpointRemoved, true)]
+        );
+    }
+
+    #[test]
+    fn parameters() {
+        let none = paths(get(json!({ "responses": {} })));
+        assert_eq!(
+            changes(none.clone(), paths(query(false))),
+            [(ChangeKind::ParameterAdded, false)]
+        );
+        assert_eq!(
+            changes(none.clone(), paths(query(true))),
+            [(ChangeKind::ParameterAdded, true)]
+        );
+        assert_eq!(
+            changes(paths(query(false)), none),
+            [(ChangeKind::ParameterRemoved, false)]
+        );
+        assert_eq!(
+            changes(paths(query(false)), paths(query(true))),
+            [(ChangeKind::ParameterRequired, true)]
+        );
+        assert_eq!(
+            changes(paths(query(true)), paths(query(false))),
+            [(ChangeKind::ParameterOptional, false)]
+        );
+    }
+
+    #[test]
+    fn request_bodies() {
+        let body = |required: bool, media_types: &[&str]| {
+            let content = media_types
+                .iter()
+                .map(|media_type| ((*media_type).to_owned(), json!({})))
+                .collect::<Map<_, _>>();
+            paths(json!({
+                "/items": {
+                    "post": {
+                        "requestBody": { "required": required, "content": content },
+                        "responses": {}
+                    }
+                }
+            }))
+        };
+        let json = ["application/json"];
+        assert_eq!(
+            changes(body(false, &json), body(true, &json)),
+            [(ChangeKind::RequestBodyRequired, true)]
+        );
+        assert_eq!(
+            changes(body(true, &json), body(false, &json)),
+            [(ChangeKind::RequestBodyOptional, false)]
+        );
+        let both = ["application/json", "application/xml"];
+        assert_eq!(
+            changes(body(true, &both), body(true, &json)),
+            [(ChangeKind::ContentRemoved, true)]
+        );
+    }
+
+    #[test]
+    fn responses() {
+        let with_content = paths(ok_response(json!({ "type": "string" })));
+        let without_content = paths(get(json!({
+            "responses": { "200": { "description": "items" } }
+        })));
+        let none = paths(get(json!({ "responses": {} })));
+        assert_eq!(
+            changes(none.clone(), with_content.clone()),
+            [(ChangeKind::ResponseAdded, false)]
+        );
+        assert_eq!(
+            changes(with_content.clone(), none),
+            [(ChangeKind::ResponseRemoved, true)]
+        );
+        assert_eq!(
+            changes(with_content, without_content),
+            [(ChangeKind::ContentRemoved, true)]
+        );
+    }
+
+    #[test]
+    fn component_schemas() {
+        let item = schemas(json!({ "Item": { "type": "object" } }));
+        assert_eq!(
+            changes(schemas(json!({})), item.clone()),
+            [(ChangeKind::SchemaAdded, false)]
+        );
+        assert_eq!(
+            changes(item.clone(), schemas(json!({}))),
+            [(ChangeKind::SchemaRemoved, true)]
+        );
+        assert_eq!(
+            changes(item, schemas(json!({ "Item": { "type": "string" } }))),
+            [(ChangeKind::SchemaChanged, true)]
+        );
+    }
+
+    #[test]
+    fn properties_depend_on_the_direction() {
+        let object = |properties: &[&str], required: &[&str]| {
+            let properties = properties
+                .iter()
+                .map(|name| ((*name).to_owned(), json!({ "type": "string" })))
+                .collect::<Map<_, _>>();
+            json!({ "type": "object", "properties": properties, "required": required })
+        };
+        let both = object(&["id", "name"], &[]);
+        let id = object(&["id"], &[]);
+        let required = object(&["id", "name"], &["name"]);
+        assert_eq!(
+            changes(paths(post_body(both.clone())), paths(post_body(id.clone()))),
+            [(ChangeKind::PropertyRemoved, false)]
+        );
+        assert_eq!(
+            changes(
+                paths(ok_response(both.clone())),
+                paths(ok_response(id.clone()))
+            ),
+            [(ChangeKind::PropertyRemoved, true)]
+        );
+        assert_eq!(
+            changes(paths(post_body(id.clone())), paths(post_body(both.clone()))),
+            [(ChangeKind::PropertyAdded, false)]
+        );
+        assert_eq!(
+            changes(
+                paths(post_body(both.clone())),
+                paths(post_body(required.clone()))
+            ),
+            [(ChangeKind::PropertyRequired, true)]
+        );
+        assert_eq!(
+            changes(
+                paths(ok_response(both.clone())),
+                paths(ok_response(required))
+            ),
+            [(ChangeKind::PropertyRequired, false)]
+        );
+        // A component may be read and sent alike
+        assert_eq!(
+            changes(
+                schemas(json!({ "Item": both })),
+                schemas(json!({ "Item": id }))
+            ),
+            [(ChangeKind::PropertyRemoved, true)]
+        );
+    }
+
+    #[test]
+    fn enums_depend_on_the_direction() {
+        let one = json!({ "type": "string", "enum": ["open"] });
+        let two = json!({ "type": "string", "enum": ["open", "done"] });
+        assert_eq!(
+            changes(paths(post_body(one.clone())), paths(post_body(two.clone()))),
+            [(ChangeKind::EnumWidened, false)]
+        );
+        assert_eq!(
+            changes(
+                paths(ok_response(one.clone())),
+                paths(ok_response(two.clone()))
+            ),
+            [(ChangeKind::EnumWidened, true)]
+        );
+        assert_eq!(
+            changes(paths(post_body(two.clone())), paths(post_body(one.clone()))),
+            [(ChangeKind::EnumNarrowed, true)]
+        );
+        assert_eq!(
+            changes(paths(ok_response(two)), paths(ok_response(one))),
+            [(ChangeKind::EnumNarrowed, false)]
+        );
+    }
+
+    #[test]
+    fn types_depend_on_the_direction() {
+        let integer = json!({ "type": "integer" });
+        let nullable = json!({ "type": ["integer", "null"] });
+        let number = json!({ "type": "number" });
+        assert_eq!(
+            changes(
+                paths(post_body(integer.clone())),
+                paths(post_body(nullable.clone()))
+            ),
+            [(ChangeKind::TypeWidened, false)]
+        );
+        assert_eq!(
+            changes(
+                paths(ok_response(integer.clone())),
+                paths(ok_response(nullable.clone()))
+            ),
+            [(ChangeKind::TypeWidened, true)]
+        );
+        assert_eq!(
+            changes(
+                paths(ok_response(integer.clone())),
+                paths(ok_response(number))
+            ),
+            [(ChangeKind::TypeWidened, true)]
+        );
+        assert_eq!(
+            changes(
+                paths(post_body(nullable.clone())),
+                paths(post_body(integer.clone()))
+            ),
+            [(ChangeKind::TypeNarrowed, true)]
+        );
+        assert_eq!(
+            changes(
+                paths(ok_response(nullable)),
+                paths(ok_response(integer.clone()))
+            ),
+            [(ChangeKind::TypeNarrowed, false)]
+        );
+        let string = json!({ "type": "string" });
+        assert_eq!(
+            changes(paths(post_body(integer)), paths(post_body(string))),
+            [(ChangeKind::SchemaChanged, true)]
+        );
+    }
+
+    #[test]
+    fn variants_are_compared() {
+        let option = |name: &str| {
+            let reference = format!("#/components/schemas/{name}");
+            json!({ "oneOf": [{ "type": "null" }, { "$ref": reference }] })
+        };
+        let named = |schema: Value| (json!({}), json!({ "Item": schema }));
+        assert!(changes(named(option("User")), named(option("User"))).is_empty());
+        assert_eq!(
+            changes(named(option("User")), named(option("Team"))),
+            [(ChangeKind::SchemaChanged, true)]
+        );
+        assert_eq!(
+            changes(
+                paths(ok_response(option("User"))),
+                paths(ok_response(option("Team")))
+            ),
+            [(ChangeKind::SchemaChanged, true)]
+        );
+
+        // Variants keep their pairs when one is inserted before them
+        let refs = |names: &[&str]| {
+            let variants = names
+                .iter()
+                .map(|name| json!({ "$ref": format!("#/components/schemas/{name}") }))
+                .collect::<Vec<_>>();
+            json!({ "oneOf": variants })
+        };
+        let two = refs(&["Cat", "Dog"]);
+        let three = refs(&["Bird", "Cat", "Dog"]);
+        assert_eq!(
+            changes(
+                paths(ok_response(two.clone())),
+                paths(ok_response(three.clone()))
+            ),
+            [(ChangeKind::TypeWidened, true)]
+        );
+        assert_eq!(
+            changes(
+                paths(post_body(two.clone())),
+                paths(post_body(three.clone()))
+            ),
+            [(ChangeKind::TypeWidened, false)]
+        );
+        assert_eq!(
+            changes(paths(post_body(three)), paths(post_body(two))),
+            [(ChangeKind::TypeNarrowed, true)]
+        );
+
+        // An `allOf` variant is one more constraint
+        let base = json!({ "allOf": [{ "type": "object" }] });
+        let both =
+            json!({ "allOf": [{ "type": "object" }, { "type": "object", "required": ["id"] }] });
+        assert_eq!(
+            changes(
+                paths(post_body(base.clone())),
+                paths(post_body(both.clone()))
+            ),
+            [(ChangeKind::TypeNarrowed, true)]
+        );
+        assert_eq!(
+            changes(paths(ok_response(base)), paths(ok_response(both))),
+            [(ChangeKind::TypeNarrowed, false)]
+        );
+    }
+}
@@ -0,0 +1,335 @@
+use serde_json::{Map, Value, json};
+
+use crate::{Scalar, Serialize};
+
+const OPENAPI_VERSION: &str = "3.0.3";
+
+// Keywords 3.0 does not know, which tools either reject or silently misread
+const UNSUPPORTED: [&str; 15] = [
+    "$schema",
+    "$id",
+    "$anchor",
+    "$defs",
+    "$comment",
+    "prefixItems",
+    "contains",
+    "propertyNames",
+    "unevaluatedProperties",
+    "unevaluatedItems",
+    "dependentRequired",
+    "dependentSchemas",
+    "if",
+    "then",
+    "else",
+];
+
+pub fn downgrade(document: &mut Value) {
+    let Some(root) = document.as_object_mut() else {
+        return;
+    };
+    root.insert("openapi".to_owned(), json!(OPENAPI_VERSION));
+    root.remove("jsonSchemaDialect");
+    root.remove("webhooks");
+    root.entry("paths").or_insert_with(|| json!({}));
+    if let Some(info) = root.get_mut("info").and_then(Value::as_object_mut) {
+        info.remove("summary");
+        if let Some(license) = info.get_mut("license").and_then(Value::as_object_mut) {
+            license.remove("identifier");
+        }
+    }
+    if let Some(components) = root.get_mut("components").and_then(Value::as_object_mut) {
+        components.remove("pathItems");
+        if let Some(schemas) = components.get_mut("schemas").and_then(Value::as_object_mut) {
+            schemas.values_mut().for_each(downgrade_schema);
+        }
+    }
+    for key in ["paths", "components"] {
+        if let Some(value) = root.get_mut(key) {
+            downgrade_schemas_in(value);
+        }
+    }
+}
+
+// Schemas sit below parameters, headers and media types, all of them under a `schema` key
+fn downgrade_schemas_in(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                match key.as_str() {
+                    "schema" => downgrade_schema(value),
+                    // Components are done above, examples are data that only look like schemas
+                    "schemas" | "example" | "examples" => {}
+                    _ => downgrade_schemas_in(value),
+                }
+            }
+        }
+        Value::Array(array) => array.iter_mut().for_each(downgrade_schemas_in),
+        _ => {}
+    }
+}
+
+fn downgrade_schema(schema: &mut Value) {
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+    for keyword in UNSUPPORTED {
+        object.remove(keyword);
+    }
+    for keyword in ["properties", "patternProperties"] {
+        if let Some(properties) = object.get_mut(keyword).and_then(Value::as_object_mut) {
+            properties.values_mut().for_each(downgrade_schema);
+        }
+    }
+    for keyword in ["items", "additionalProperties", "not"] {
+        if let Some(value) = object.get_mut(keyword) {
+            downgrade_schema(value);
+        }
+    }
+    for keyword in ["allOf", "anyOf", "oneOf"] {
+        if let Some(schemas) = object.get_mut(keyword).and_then(Value::as_array_mut) {
+            schemas.iter_mut().for_each(downgrade_schema);
+        }
+    }
+    downgrade_type(object);
+    downgrade_null_variant(object, "oneOf");
+    downgrade_null_variant(object, "anyOf");
+    let example = object
+        .remove("examples")
+        .and_then(|examples| examples.as_array()?.first().cloned());
+    if let Some(example) = example {
+        object.insert("example".to_owned(), example);
+    }
+    if let Some(value) = object.remove("const") {
+        object.insert("enum".to_owned(), json!([value]));
+    }
+    for (exclusive, inclusive) in [
+        ("exclusiveMinimum", "minimum"),
+        ("exclusiveMaximum", "maximum"),
+    ] {
+        if let Some(bound) = object.get(exclusive).filter(|bound| bound.is_number()) {
+            let bound = bound.clone();
+            object.insert(inclusive.to_owned(), bound);
+            object.insert(exclusive.to_owned(), json!(true));
+        }
+    }
+    downgrade_content(object);
+    // 3.0 ignores everything next to a reference, so the reference moves into `allOf`
+    if object.len() > 1 && object.contains_key("$ref") {
+        let reference = object.remove("$ref").unwrap_or_default();
+        object.insert("allOf".to_owned(), json!([{ "$ref": reference }]));
+    }
+}
+
+// `type: [x, "null"]` becomes `type: x` and `nullable: true`, several types become `anyOf`
+fn downgrade_type(object: &mut Map<String, Value>) {
+    let types = match object.get("type") {
+        Some(Value::Array(types)) => types.clone(),
+        Some(Value::String(kind)) if kind == "null" => vec![json!("null")],
+        _ => return,
+    };
+    object.remove("type");
+    let (nulls, types): (Vec<_>, Vec<_>) = types.into_iter().partition(|kind| kind == "null");
+    if !nulls.is_empty() {
+        object.insert("nullable".to_owned(), json!(true));
+    }
+    match types.as_slice() {
+        [] => {}
+        [kind] => {
+            object.insert("type".to_owned(), kind.clone());
+        }
+        types => {
+            let variants = types.iter().map(|kind| json!({ "type": kind })).collect();
+            object.insert("anyOf".to_owned(), Value::Array(variants));
+        }
+    }
+}
+
+// `oneOf: [{ type: "null" }, x]` is how an optional reference is written in 3.1
+fn downgrade_null_variant(object: &mut Map<String, Value>, keyword: &str) {
+    let Some(variants) = object.get_mut(keyword).and_then(Value::as_array_mut) else {
+        return;
+    };
+    let len = variants.len();
+    variants.retain(|variant| !is_null_schema(variant));
+    if variants.len() == len {
+        return;
+    }
+    let variants = object.remove(keyword).unwrap_or_default();
+    object.insert("nullable".to_owned(), json!(true));
+    match variants {
+        // `nullable` has no effect next to a lone reference, but it does next to `allOf`
+        Value::Array(variants) if variants.len() == 1 => {
+            object.insert("allOf".to_owned(), Value::Array(variants));
+        }
+        Value::Array(variants) if variants.is_empty() => {}
+        variants => {
+            object.insert(keyword.to_owned(), variants);
+        }
+    }
+}
+
+fn is_null_schema(schema: &Value) -> bool {
+    match schema.as_object() {
+        Some(object) => {
+            object.get("type") == Some(&json!("null"))
+                || object.get("nullable") == Some(&json!(true)) && object.len() == 1
+        }
+        None => false,
+    }
+}
+
+// Binary content is described by its encoding in 3.1, and by its format in 3.0
+fn downgrade_content(object: &mut Map<String, Value>) {
+    let encoding = object.remove("contentEncoding");
+    let media_type = object.remove("contentMediaType");
+    let format = match (encoding, media_type) {
+        (Some(encoding), _) if encoding == "base64" => "byte",
+        (None, Some(_)) => "binary",
+        _ => return,
+    };
+    object.entry("type").or_insert_with(|| json!("string"));
+    object.insert("format".to_owned(), json!(format));
+}
+
+impl<S: Serialize> Scalar<S> {
+    // Serves a 3.0 copy of the document next to the 3.1 one, for tools that only read 3.0
+    pub fn with_openapi_30(mut self) -> Self {
+        self.openapi_30 = true;
+        self
+    }
+
+    pub(crate) fn api_json_30(&self) -> String {
+        let api_json = self.api_json();
+        // A raw document that is not json cannot be converted, it is passed on the way it is
+        match serde_json::from_str::<Value>(&api_json) {
+            Ok(mut document) => {
+                downgrade(&mut document);
+                document.to_string()
+            }
+            Err(_) => api_json,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schemas() {
+        let table = [
+            (
+                json!({ "type": ["string", "null"] }),
+                json!({ "type": "string", "nullable": true }),
+            ),
+            (
+                json!({ "type": ["string", "integer"] }),
+                json!({ "anyOf": [{ "type": "string" }, { "type": "integer" }] }),
+            ),
+            (
+                json!({ "type": ["string", "integer", "null"] }),
+                json!({
+                    "anyOf": [{ "type": "string" }, { "type": "integer" }],
+                    "nullable": true
+                }),
+            ),
+            (
+                json!({ "oneOf": [{ "type": "null" }, { "$ref": "#/components/schemas/Item" }] }),
+                json!({ "allOf": [{ "$ref": "#/components/schemas/Item" }], "nullable": true }),
+            ),
+            (json!({ "const": "open" }), json!({ "enum": ["open"] })),
+            (
+                json!({ "type": "integer", "exclusiveMinimum": 0, "exclusiveMaximum": 10 }),
+                json!({
+                    "type": "integer",
+                    "minimum": 0,
+                    "exclusiveMinimum": true,
+                    "maximum": 10,
+                    "exclusiveMaximum": true
+                }),
+            ),
+            (
+                json!({ "$ref": "#/components/schemas/Item", "description": "the item" }),
+                json!({
+                    "allOf": [{ "$ref": "#/components/schemas/Item" }],
+                    "description": "the item"
+                }),
+            ),
+            (
+                json!({ "$ref": "#/components/schemas/Item" }),
+                json!({ "$ref": "#/components/schemas/Item" }),
+            ),
+            (
+                json!({ "type": "string", "examples": ["a", "b"] }),
+                json!({ "type": "string", "example": "a" }),
+            ),
+            (
+                json!({ "contentMediaType": "image/png" }),
+                json!({ "type": "string", "format": "binary" }),
+            ),
+            (
+                json!({
+                    "type": "object",
+                    "properties": { "id": { "type": ["integer", "null"] } }
+                }),
+                json!({
+                    "type": "object",
+                    "properties": { "id": { "type": "integer", "nullable": true } }
+                }),
+            ),
+        ];
+        for (schema, expected) in table {
+            let mut document = json!({
+                "openapi": "3.1.0",
+                "components": { "schemas": { "Item": schema.clone() } }
+            });
+            downgrade(&mut document);
+            assert_eq!(
+                document["components"]["schemas"]["Item"], expected,
+                "{schema}"
+            );
+        }
+    }
+
+    #[test]
+    fn schemas_below_paths() {
+        let mut document = json!({
+            "openapi": "3.1.0",
+            "paths": {
+                "/items": {
+                    "get": {
+                        "parameters": [{
+                            "name": "page",
+                            "in": "query",
+                            "schema": { "type": ["integer", "null"] }
+                        }],
+                        "responses": {
+                            "200": {
+                                "description": "items",
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "const": "open" },
+                                        "example": { "type": ["not", "a", "schema"] }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        downgrade(&mut document);
+        assert_eq!(document["openapi"], OPENAPI_VERSION);
+        let operation = &document["paths"]["/items"]["get"];
+        assert_eq!(
+            operation["parameters"][0]["schema"],
+            json!({ "type": "integer", "nullable": true })
+        );
+        let content = &operation["responses"]["200"]["content"]["application/json"];
+        assert_eq!(content["schema"], json!({ "enum": ["open"] }));
+        assert_eq!(
+            content["example"],
+            json!({ "type": ["not", "a", "schema"] })
+        );
+    }
+}
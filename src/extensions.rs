@@ -0,0 +1,428 @@
+use serde::Serialize as SerdeSerialize;
+use serde_json::Value;
+use utoipa::Modify;
+use utoipa::openapi::OpenApi;
+use utoipa::openapi::extensions::Extensions;
+use utoipa::openapi::tag::Tag;
+
+use crate::spec::operations_mut;
+
+const X_TAG_GROUPS: &str = "x-tagGroups";
+const X_DISPLAY_NAME: &str = "x-displayName";
+const X_SCALAR_IGNORE: &str = "x-scalar-ignore";
+const X_CODE_SAMPLES: &str = "x-codeSamples";
+const X_BADGES: &str = "x-badges";
+
+// The fields an entry is known by, applying a modifier again replaces its entries instead of
+// adding them twice
+pub(crate) const CODE_SAMPLE_KEY: &[&str] = &["lang", "label"];
+const BADGE_KEY: &[&str] = &["name"];
+
+#[derive(SerdeSerialize, Debug, Clone)]
+struct TagGroup {
+    name: String,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TagGroups {
+    groups: Vec<TagGroup>,
+}
+
+impl TagGroups {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn group<I, T>(mut self, name: impl AsRef<str>, tags: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        self.groups.push(TagGroup {
+            name: name.as_ref().to_owned(),
+            tags: tags
+                .into_iter()
+                .map(|tag| tag.as_ref().to_owned())
+                .collect(),
+        });
+        self
+    }
+}
+
+impl Modify for TagGroups {
+    fn modify(&self, openapi: &mut OpenApi) {
+        let groups = serde_json::to_value(&self.groups).unwrap();
+        insert_extension(&mut openapi.extensions, X_TAG_GROUPS, groups);
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DisplayNames {
+    names: Vec<(String, String)>,
+}
+
+impl DisplayNames {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn tag(mut self, tag: impl AsRef<str>, display_name: impl AsRef<str>) -> Self {
+        self.names
+            .push((tag.as_ref().to_owned(), display_name.as_ref().to_owned()));
+        self
+    }
+}
+
+impl Modify for DisplayNames {
+    fn modify(&self, openapi: &mut OpenApi) {
+        for (name, display_name) in &self.names {
+            let tag = tag_mut(openapi, name);
+            insert_extension(
+                &mut tag.extensions,
+                X_DISPLAY_NAME,
+                Value::String(display_name.clone()),
+            );
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ScalarIgnore {
+    operations: Vec<String>,
+    tags: Vec<String>,
+}
+
+impl ScalarIgnore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn operation(mut self, operation_id: impl AsRef<str>) -> Self {
+        self.operations.push(operation_id.as_ref().to_owned());
+        self
+    }
+
+    pub fn tag(mut self, tag: impl AsRef<str>) -> Self {
+        self.tags.push(tag.as_ref().to_owned());
+        self
+    }
+}
+
+impl Modify for ScalarIgnore {
+    fn modify(&self, openapi: &mut OpenApi) {
+        for operation in operations_mut(openapi) {
+            if operation
+                .operation_id
+                .as_ref()
+                .is_some_and(|id| self.operations.contains(id))
+            {
+                insert_extension(
+                    &mut operation.extensions,
+                    X_SCALAR_IGNORE,
+                    Value::Bool(true),
+                );
+            }
+        }
+        for name in &self.tags {
+            let tag = tag_mut(openapi, name);
+            insert_extension(&mut tag.extensions, X_SCALAR_IGNORE, Value::Bool(true));
+        }
+    }
+}
+
+#[derive(SerdeSerialize, Debug, Clone)]
+pub struct CodeSample {
+    lang: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    source: String,
+}
+
+impl CodeSample {
+    pub fn new(lang: impl AsRef<str>, source: impl AsRef<str>) -> Self {
+        Self {
+            lang: lang.as_ref().to_owned(),
+            label: None,
+            source: source.as_ref().to_owned(),
+        }
+    }
+
+    pub fn label(mut self, label: impl AsRef<str>) -> Self {
+        self.label = Some(label.as_ref().to_owned());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CodeSamples {
+    samples: Vec<(String, CodeSample)>,
+}
+
+impl CodeSamples {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sample(mut self, operation_id: impl AsRef<str>, sample: CodeSample) -> Self {
+        self.samples
+            .push((operation_id.as_ref().to_owned(), sample));
+        self
+    }
+}
+
+impl Modify for CodeSamples {
+    fn modify(&self, openapi: &mut OpenApi) {
+        for operation in operations_mut(openapi) {
+            let Some(operation_id) = operation.operation_id.as_ref() else {
+                continue;
+            };
+            let samples = self
+                .samples
+                .iter()
+                .filter(|(id, _)| id == operation_id)
+                .map(|(_, sample)| serde_json::to_value(sample).unwrap())
+                .collect::<Vec<_>>();
+            append_extension(
+                &mut operation.extensions,
+                X_CODE_SAMPLES,
+                samples,
+                CODE_SAMPLE_KEY,
+            );
+        }
+    }
+}
+
+#[derive(SerdeSerialize, Debug, Clone)]
+pub struct Badge {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<String>,
+}
+
+impl Badge {
+    pub fn new(name: impl AsRef<str>) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            position: None,
+            color: None,
+        }
+    }
+
+    pub fn position(mut self, position: impl AsRef<str>) -> Self {
+        self.position = Some(position.as_ref().to_owned());
+        self
+    }
+
+    pub fn color(mut self, color: impl AsRef<str>) -> Self {
+        self.color = Some(color.as_ref().to_owned());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Badges {
+    badges: Vec<(String, Badge)>,
+}
+
+impl Badges {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn badge(mut self, operation_id: impl AsRef<str>, badge: Badge) -> Self {
+        self.badges.push((operation_id.as_ref().to_owned(), badge));
+        self
+    }
+}
+
+impl Modify for Badges {
+    fn modify(&self, openapi: &mut OpenApi) {
+        for operation in operations_mut(openapi) {
+            let Some(operation_id) = operation.operation_id.as_ref() else {
+                continue;
+            };
+            let badges = self
+                .badges
+                .iter()
+                .filter(|(id, _)| id == operation_id)
+                .map(|(_, badge)| serde_json::to_value(badge).unwrap())
+                .collect::<Vec<_>>();
+            append_extension(&mut operation.extensions, X_BADGES, badges, BADGE_KEY);
+        }
+    }
+}
+
+fn tag_mut<'a>(openapi: &'a mut OpenApi, name: &str) -> &'a mut Tag {
+    let tags = openapi.tags.get_or_insert_with(Vec::new);
+    match tags.iter().position(|tag| tag.name == name) {
+        Some(index) => &mut tags[index],
+        None => {
+            tags.push(Tag::new(name));
+            tags.last_mut().unwrap()
+        }
+    }
+}
+
+fn insert_extension(extensions: &mut Option<Extensions>, key: &str, value: Value) {
+    extensions
+        .get_or_insert_with(Extensions::default)
+        .insert(key.to_owned(), value);
+}
+
+// Keep the entries that were already attached to the operation, so several modifiers can
+// contribute to the same extension, and replace the ones that share the fields in `identity`
+pub(crate) fn append_extension(
+    extensions: &mut Option<Extensions>,
+    key: &str,
+    values: Vec<Value>,
+    identity: &[&str],
+) {
+    if values.is_empty() {
+        return;
+    }
+    let extensions = extensions.get_or_insert_with(Extensions::default);
+    if !extensions.get(key).is_some_and(Value::is_array) {
+        extensions.insert(key.to_owned(), Value::Array(Vec::new()));
+    }
+    let Some(Value::Array(existing)) = extensions.get_mut(key) else {
+        return;
+    };
+    for value in values {
+        let same = |entry: &Value| {
+            identity
+                .iter()
+                .all(|field| entry.get(field) == value.get(field))
+        };
+        match existing.iter_mut().find(|entry| same(entry)) {
+            Some(entry) => *entry = value,
+            None => existing.push(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn openapi() -> OpenApi {
+        serde_json::from_value(json!({
+            "openapi": "3.1.0",
+            "info": { "title": "items", "version": "1" },
+            "paths": {
+                "/items": {
+                    "get": { "operationId": "list", "responses": {} },
+                    "post": { "operationId": "create", "responses": {} }
+                }
+            },
+            "tags": [{ "name": "items" }]
+        }))
+        .unwrap()
+    }
+
+    // Every modifier is applied twice, which has to leave the document as it was after the first
+    fn modified(modifier: &impl Modify) -> Value {
+        let mut openapi = openapi();
+        modifier.modify(&mut openapi);
+        let once = serde_json::to_value(&openapi).unwrap();
+        modifier.modify(&mut openapi);
+        assert_eq!(serde_json::to_value(&openapi).unwrap(), once);
+        once
+    }
+
+    #[test]
+    fn tag_groups() {
+        let document = modified(&TagGroups::new().group("Store", ["items", "orders"]));
+        assert_eq!(
+            document[X_TAG_GROUPS],
+            json!([{ "name": "Store", "tags": ["items", "orders"] }])
+        );
+    }
+
+    #[test]
+    fn display_names() {
+        let names = DisplayNames::new()
+            .tag("items", "All items")
+            .tag("orders", "Orders");
+        let document = modified(&names);
+        assert_eq!(
+            document["tags"],
+            json!([
+                { "name": "items", X_DISPLAY_NAME: "All items" },
+                { "name": "orders", X_DISPLAY_NAME: "Orders" }
+            ])
+        );
+    }
+
+    #[test]
+    fn scalar_ignore() {
+        let document = modified(&ScalarIgnore::new().operation("create").tag("items"));
+        let operations = &document["paths"]["/items"];
+        assert_eq!(operations["post"][X_SCALAR_IGNORE], true);
+        assert!(operations["get"].get(X_SCALAR_IGNORE).is_none());
+        assert_eq!(document["tags"][0][X_SCALAR_IGNORE], true);
+    }
+
+    #[test]
+    fn code_samples() {
+        let samples = CodeSamples::new()
+            .sample(
+                "list",
+                CodeSample::new("shell", "curl /items").label("curl"),
+            )
+            .sample(
+                "list",
+                CodeSample::new("shell", "http /items").label("httpie"),
+            );
+        let document = modified(&samples);
+        assert_eq!(
+            document["paths"]["/items"]["get"][X_CODE_SAMPLES],
+            json!([
+                { "lang": "shell", "label": "curl", "source": "curl /items" },
+                { "lang": "shell", "label": "httpie", "source": "http /items" }
+            ])
+        );
+        assert!(
+            document["paths"]["/items"]["post"]
+                .get(X_CODE_SAMPLES)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn code_samples_replace_their_own_entries() {
+        let mut openapi = openapi();
+        let curl = |source| CodeSamples::new().sample("list", CodeSample::new("shell", source));
+        curl("curl /items").modify(&mut openapi);
+        let rust = CodeSamples::new().sample("list", CodeSample::new("rust", "reqwest"));
+        rust.modify(&mut openapi);
+        curl("curl -s /items").modify(&mut openapi);
+        let document = serde_json::to_value(&openapi).unwrap();
+        assert_eq!(
+            document["paths"]["/items"]["get"][X_CODE_SAMPLES],
+            json!([
+                { "lang": "shell", "source": "curl -s /items" },
+                { "lang": "rust", "source": "reqwest" }
+            ])
+        );
+    }
+
+    #[test]
+    fn badges() {
+        let badges = Badges::new()
+            .badge("create", Badge::new("Beta").color("orange"))
+            .badge("create", Badge::new("Admin").position("before"));
+        let document = modified(&badges);
+        assert_eq!(
+            document["paths"]["/items"]["post"][X_BADGES],
+            json!([
+                { "name": "Beta", "color": "orange" },
+                { "name": "Admin", "position": "before" }
+            ])
+        );
+    }
+}
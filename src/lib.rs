@@ -4,26 +4,124 @@ mod actix;
 #[cfg(feature = "axum")]
 mod axum;
 
+#[cfg(feature = "ntex")]
+mod ntex;
+
+#[cfg(feature = "poem")]
+mod poem;
+
 #[cfg(feature = "rocket")]
 mod rocket;
 
+#[cfg(feature = "salvo")]
+mod salvo;
+
+#[cfg(feature = "tower")]
+mod tower;
+
+#[cfg(feature = "warp")]
+mod warp;
+
+pub mod build;
+mod changelog;
+mod cors;
+pub mod diff;
+pub mod downgrade;
+mod extensions;
+pub mod lint;
+#[cfg(any(feature = "actix-web", feature = "salvo"))]
+mod rebased;
+mod rust_samples;
+mod service;
+mod spec;
+mod standalone;
+mod tenant;
+mod validate;
+mod versioned;
+
+pub use cors::CorsPolicy;
+pub use extensions::{
+    Badge, Badges, CodeSample, CodeSamples, DisplayNames, ScalarIgnore, TagGroups,
+};
+pub use rust_samples::{RustCodeSamples, RustSample, SampleParam};
+pub use service::ScalarService;
+pub use tenant::{Tenant, TenantResolver};
+pub use validate::{Diagnostic, ValidationError};
+
+#[cfg(feature = "rocket")]
+pub use crate::rocket::ScalarFairing;
+
+use build::Prebuilt;
+use changelog::Changelog;
+use lint::LintReport;
 use maud::{DOCTYPE, Markup, html};
-use serde::Serialize as SerdeSerialize;
+use serde::{Deserialize, Serialize as SerdeSerialize};
 use serde_json::Value;
+use serde_json::value::RawValue;
 use std::borrow::Cow;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use tenant::Tenants;
+use utoipa::Modify;
 use utoipa::openapi::OpenApi;
+use versioned::{VersionOf, Versions};
 
 const SCALAR_API_REFERENCE_JS: &str = include_str!("../static/scalar-api-reference.js");
 
 const SCALAR_SCRIPT: &str = "scalar-api-reference.js";
 const OPENAPI_JSON: &str = "api-docs/openapi.json";
+const OPENAPI_30_JSON: &str = "api-docs/openapi-3.0.json";
+const CHANGELOG: &str = "changelog";
 
-pub trait Serialize: SerdeSerialize {}
+pub trait Serialize: SerdeSerialize {
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
 
 impl Serialize for OpenApi {}
 
 impl Serialize for Value {}
 
+// Raw json is served as it is, serializing it would turn it into a json string
+impl Serialize for str {
+    fn to_json(&self) -> String {
+        self.to_owned()
+    }
+}
+
+impl Serialize for String {
+    fn to_json(&self) -> String {
+        self.clone()
+    }
+}
+
+impl Serialize for RawValue {
+    fn to_json(&self) -> String {
+        self.get().to_owned()
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for &T {
+    fn to_json(&self) -> String {
+        (**self).to_json()
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for Box<T> {
+    fn to_json(&self) -> String {
+        (**self).to_json()
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for Arc<T> {
+    fn to_json(&self) -> String {
+        (**self).to_json()
+    }
+}
+
 #[non_exhaustive]
 #[derive(Debug, Clone)]
 pub struct Scalar<S: Serialize> {
@@ -31,6 +129,14 @@ pub struct Scalar<S: Serialize> {
     title: Cow<'static, str>,
     openapi: S,
     config: Config,
+    spec_cors: Option<CorsPolicy>,
+    prebuilt: Option<Prebuilt>,
+    lint_banner: Option<LintReport>,
+    changelog: Option<Changelog>,
+    versions: Option<Versions>,
+    version_of: Option<VersionOf>,
+    tenants: Option<Tenants>,
+    openapi_30: bool,
 }
 
 impl<S: Serialize> Scalar<S> {
@@ -40,6 +146,14 @@ impl<S: Serialize> Scalar<S> {
             title: Cow::Borrowed("Scalar"),
             openapi,
             config: Config::default(),
+            spec_cors: None,
+            prebuilt: None,
+            lint_banner: None,
+            changelog: None,
+            versions: None,
+            version_of: None,
+            tenants: None,
+            openapi_30: false,
         }
     }
 
@@ -51,8 +165,11 @@ impl<S: Serialize> Scalar<S> {
         self
     }
 
-    pub fn with_title(mut self, title: &'static str) -> Self {
-        self.title = Cow::Borrowed(title);
+    pub fn with_title<T>(mut self, title: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.title = title.into();
         self
     }
 
@@ -61,30 +178,115 @@ impl<S: Serialize> Scalar<S> {
         self
     }
 
+    pub fn with_spec_cors(mut self, policy: CorsPolicy) -> Self {
+        self.spec_cors = Some(policy);
+        self
+    }
+
+    // Serializes the document once, for pages that are built from several documents. The json is
+    // kept as text rather than parsed, so a raw spec that is not json is served the way it is
+    fn into_raw(self) -> Scalar<Box<str>> {
+        let api_json = self.api_json().into_boxed_str();
+        Scalar {
+            url: self.url,
+            title: self.title,
+            openapi: api_json,
+            config: self.config,
+            spec_cors: self.spec_cors,
+            prebuilt: None,
+            lint_banner: self.lint_banner,
+            changelog: self.changelog,
+            versions: None,
+            version_of: None,
+            tenants: None,
+            openapi_30: self.openapi_30,
+        }
+    }
+
     fn config_json(&self) -> String {
         serde_json::to_string(&self.config).unwrap()
     }
 
     fn script_url(&self) -> String {
-        let url = self.url.as_ref();
+        // The versions share the script of the documentation they belong to
+        if let Some(version_of) = &self.version_of {
+            return version_of.script_url.clone();
+        }
+        let url = self.url.trim_end_matches('/');
         format!("{url}/{SCALAR_SCRIPT}")
     }
 
     fn api_json(&self) -> String {
-        serde_json::to_string(&self.openapi).unwrap()
+        match &self.prebuilt {
+            Some(prebuilt) => prebuilt.api_json.to_owned(),
+            None => self.openapi.to_json(),
+        }
+    }
+
+    // The prebuilt page is only good as long as nothing it was rendered from has changed since
+    fn prebuilt_markup(&self) -> Option<&Prebuilt> {
+        self.prebuilt.as_ref().filter(|prebuilt| {
+            prebuilt.url == self.url
+                && prebuilt.title == self.title
+                && prebuilt.config == self.config_json()
+                && self.lint_banner.is_none()
+        })
     }
 
     fn api_json_url(&self) -> String {
-        let url = self.url.as_ref();
+        let url = self.url.trim_end_matches('/');
         format!("{url}/{OPENAPI_JSON}")
     }
 
-    fn markup(&self) -> Markup {
+    fn api_json_30_url(&self) -> String {
+        let url = self.url.trim_end_matches('/');
+        format!("{url}/{OPENAPI_30_JSON}")
+    }
+
+    fn changelog_url(&self) -> String {
+        let url = self.url.trim_end_matches('/');
+        format!("{url}/{CHANGELOG}")
+    }
+
+    pub fn export_to_dir(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        // Tenants are only known once a request comes in, there is nothing to write for them
+        if self.tenants.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "documentation resolved per tenant cannot be exported",
+            ));
+        }
+        let dir = path.as_ref();
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(SCALAR_SCRIPT), SCALAR_API_REFERENCE_JS)?;
+        match &self.versions {
+            Some(versions) => self.export_versions(versions, dir),
+            None => self.export_page(dir, SCALAR_SCRIPT),
+        }
+    }
+
+    // Relative urls keep the bundle working no matter where the static server mounts it
+    fn export_page(&self, dir: &Path, script_src: &str) -> io::Result<()> {
+        let api_json = dir.join(OPENAPI_JSON);
+        if let Some(parent) = api_json.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let markup = self.render(OPENAPI_JSON, script_src);
+        fs::write(dir.join("index.html"), markup.0)?;
+        if self.openapi_30 {
+            fs::write(dir.join(OPENAPI_30_JSON), self.api_json_30())?;
+        }
+        // Static servers answer `/changelog` with `changelog.html`, the way the route is served
+        if let Some(changelog) = &self.changelog {
+            let markup = changelog.render(&self.title, "index.html");
+            fs::write(dir.join(format!("{CHANGELOG}.html")), markup.0)?;
+        }
+        fs::write(api_json, self.api_json())
+    }
+
+    fn render(&self, data_url: &str, script_src: &str) -> Markup {
         let config = self.config_json();
         let title = self.title.as_ref();
-        let url = self.url.as_ref();
-        let data_url = format!("{url}/{OPENAPI_JSON}");
-        let script_src = format!("{url}/{SCALAR_SCRIPT}");
         html! {
             (DOCTYPE)
             head {
@@ -98,22 +300,47 @@ impl<S: Serialize> Scalar<S> {
                 }
                 script src=(script_src) type="module" {
                 }
+                (self.lint_banner_markup())
+                (self.version_selector_markup())
             }
         }
     }
 }
 
-#[derive(SerdeSerialize, Debug, Clone, Default)]
+// The document is the prebuilt json itself, so exporting and converting it work like on any raw spec
+impl Scalar<&'static str> {
+    pub fn from_prebuilt(prebuilt: Prebuilt) -> Self {
+        // `emit_spec` wrote the config, one that does not parse was edited or comes from another
+        // version of the crate
+        let config = serde_json::from_str(prebuilt.config)
+            .expect("the prebuilt Scalar config is malformed, run `build::emit_spec` again");
+        let mut scalar = Scalar::new(prebuilt.api_json)
+            .with_url(prebuilt.url)
+            .with_title(prebuilt.title)
+            .with_config(config);
+        scalar.prebuilt = Some(prebuilt);
+        scalar
+    }
+}
+
+impl Scalar<OpenApi> {
+    pub fn with_modifier<M: Modify>(mut self, modifier: M) -> Self {
+        modifier.modify(&mut self.openapi);
+        self
+    }
+}
+
+#[derive(SerdeSerialize, Deserialize, Debug, Clone, Default)]
 pub struct MetaInfo {
     title: String,
     description: String,
-    #[serde(rename(serialize = "ogDescription"))]
+    #[serde(rename(serialize = "ogDescription", deserialize = "ogDescription"))]
     og_description: String,
-    #[serde(rename(serialize = "ogTitle"))]
+    #[serde(rename(serialize = "ogTitle", deserialize = "ogTitle"))]
     og_title: String,
-    #[serde(rename(serialize = "ogImage"))]
+    #[serde(rename(serialize = "ogImage", deserialize = "ogImage"))]
     og_image: String,
-    #[serde(rename(serialize = "twitterCard"))]
+    #[serde(rename(serialize = "twitterCard", deserialize = "twitterCard"))]
     twitter_card: String,
 }
 
@@ -149,26 +376,33 @@ impl MetaInfo {
     }
 }
 
-#[derive(SerdeSerialize, Debug, Clone)]
+#[derive(SerdeSerialize, Deserialize, Debug, Clone)]
 pub struct Config {
     theme: String,
-    #[serde(rename(serialize = "isEditable"))]
+    #[serde(rename(serialize = "isEditable", deserialize = "isEditable"))]
     is_editable: bool,
-    #[serde(rename(serialize = "hideModels"))]
+    #[serde(rename(serialize = "hideModels", deserialize = "hideModels"))]
     hide_models: bool,
-    #[serde(rename(serialize = "hideClientButton"))]
+    #[serde(rename(serialize = "hideClientButton", deserialize = "hideClientButton"))]
     hide_client_button: bool,
-    #[serde(rename(serialize = "hideClients"))]
+    #[serde(rename(serialize = "hideClients", deserialize = "hideClients"))]
     hidden_clients: bool,
-    #[serde(rename(serialize = "defaultOpenAllTags"))]
+    #[serde(rename(serialize = "defaultOpenAllTags", deserialize = "defaultOpenAllTags"))]
     default_open_all_tags: bool,
-    #[serde(rename(serialize = "showSidebar"))]
+    #[serde(rename(serialize = "showSidebar", deserialize = "showSidebar"))]
     show_sidebar: bool,
     #[serde(
-        rename(serialize = "metaData"),
-        skip_serializing_if = "Option::is_none"
+        rename(serialize = "metaData", deserialize = "metaData"),
+        skip_serializing_if = "Option::is_none",
+        default
     )]
     meta_data: Option<MetaInfo>,
+    #[serde(
+        rename(serialize = "customCss", deserialize = "customCss"),
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    custom_css: Option<String>,
 }
 
 impl Default for Config {
@@ -182,6 +416,7 @@ impl Default for Config {
             default_open_all_tags: false,
             show_sidebar: true,
             meta_data: None,
+            custom_css: None,
         }
     }
 }
@@ -226,4 +461,92 @@ impl Config {
         self.meta_data = Some(meta_data);
         self
     }
+
+    pub fn custom_css(mut self, custom_css: impl AsRef<str>) -> Self {
+        self.custom_css = Some(custom_css.as_ref().to_owned());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn read(dir: &Path, path: &str) -> String {
+        fs::read_to_string(dir.join(path)).unwrap()
+    }
+
+    fn openapi() -> OpenApi {
+        serde_json::from_value(json!({
+            "openapi": "3.1.0",
+            "info": { "title": "items", "version": "1" },
+            "paths": { "/items": { "get": { "tags": ["items"], "responses": {} } } }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn exports_a_bundle_with_relative_urls() {
+        let dir = tempfile::tempdir().unwrap();
+        let openapi = openapi();
+        Scalar::new(openapi.clone())
+            .with_url("/scalar")
+            .with_openapi_30()
+            .with_history(vec![("1", OpenApi::default()), ("2", openapi)])
+            .export_to_dir(dir.path())
+            .unwrap();
+        let dir = dir.path();
+        let page = read(dir, "index.html");
+        assert!(page.contains(r#"data-url="api-docs/openapi.json""#));
+        assert!(page.contains(r#"src="scalar-api-reference.js""#));
+        assert_eq!(read(dir, SCALAR_SCRIPT), SCALAR_API_REFERENCE_JS);
+        assert!(read(dir, OPENAPI_JSON).starts_with(r#"{"openapi":"3.1.0""#));
+        assert!(read(dir, OPENAPI_30_JSON).contains(r#""openapi":"3.0.3""#));
+        let changelog = read(dir, "changelog.html");
+        assert!(changelog.contains(r#"href="index.html#tag/items/GET/items""#));
+    }
+
+    #[test]
+    fn exports_every_version() {
+        let dir = tempfile::tempdir().unwrap();
+        Scalar::versioned()
+            .with_url("/scalar")
+            .with_openapi_30()
+            .with_version("v1", Scalar::new(json!({ "version": 1 })))
+            .with_version("v2", Scalar::new(json!({ "version": 2 })))
+            .with_default_version("v2")
+            .export_to_dir(dir.path())
+            .unwrap();
+        let dir = dir.path();
+        assert!(read(dir, "index.html").contains(r#"content="0; url=v2/""#));
+        assert!(!dir.join(OPENAPI_JSON).exists());
+        assert_eq!(read(dir, "v1/api-docs/openapi.json"), r#"{"version":1}"#);
+        assert!(dir.join("v1/api-docs/openapi-3.0.json").exists());
+        let page = read(dir, "v2/index.html");
+        assert!(page.contains(r#"data-url="api-docs/openapi.json""#));
+        assert!(page.contains(r#"src="../scalar-api-reference.js""#));
+        assert!(page.contains(r#"<option value="../v1/">v1</option>"#));
+        assert!(page.contains(r#"<option value="../v2/" selected>v2</option>"#));
+        assert!(dir.join(SCALAR_SCRIPT).exists());
+    }
+
+    #[test]
+    fn refuses_what_cannot_be_exported() {
+        struct Tenants;
+
+        impl TenantResolver for Tenants {
+            fn resolve(&self, _: &str) -> Option<Tenant> {
+                None
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let tenants = Scalar::new(json!({})).with_tenants(Tenants);
+        let error = tenants.export_to_dir(dir.path()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+        let empty = Scalar::versioned().export_to_dir(dir.path()).unwrap_err();
+        assert_eq!(empty.kind(), io::ErrorKind::InvalidInput);
+    }
 }
@@ -0,0 +1,623 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use maud::{Markup, html};
+use utoipa::openapi::path::Operation;
+use utoipa::openapi::schema::Schema;
+use utoipa::openapi::{OpenApi, RefOr};
+
+use crate::spec::{path_operations, resolve_schema};
+use crate::{Scalar, Serialize};
+
+// Fixed to the bottom, so the layout of the page underneath stays as it is
+const BANNER_STYLE: &str = "position: fixed; bottom: 0; left: 0; right: 0; z-index: 10000; \
+    max-height: 30vh; overflow: auto; padding: 8px 16px; background: #fff4e5; color: #663c00; \
+    border-top: 1px solid #ffb74d; font: 13px sans-serif;";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Off,
+    Info,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self {
+            Severity::Off => "off",
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        f.write_str(severity)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    location: String,
+    message: String,
+}
+
+impl Violation {
+    pub fn new(location: impl AsRef<str>, message: impl AsRef<str>) -> Self {
+        Self {
+            location: location.as_ref().to_owned(),
+            message: message.as_ref().to_owned(),
+        }
+    }
+}
+
+pub trait LintRule {
+    fn name(&self) -> &str;
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, openapi: &OpenApi) -> Vec<Violation>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    rule: String,
+    severity: Severity,
+    location: String,
+    message: String,
+}
+
+impl Finding {
+    pub fn rule(&self) -> &str {
+        &self.rule
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [{}] {}: {}",
+            self.severity, self.rule, self.location, self.message
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintReport {
+    findings: Vec<Finding>,
+}
+
+impl LintReport {
+    pub fn findings(&self) -> &[Finding] {
+        &self.findings
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    // Info findings are hints, they do not make a document unclean
+    pub fn is_clean(&self) -> bool {
+        self.findings
+            .iter()
+            .all(|finding| finding.severity < Severity::Warning)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.findings
+            .iter()
+            .any(|finding| finding.severity == Severity::Error)
+    }
+}
+
+impl fmt::Display for LintReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for finding in &self.findings {
+            writeln!(f, "{finding}")?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Linter {
+    rules: Vec<(Box<dyn LintRule + Send + Sync>, Severity)>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::empty()
+            .rule(OperationSummary)
+            .rule(KebabCasePaths)
+            .rule(DeclaredTags)
+            .rule(RequestBodyExamples)
+            .rule(ErrorResponseDescriptions)
+    }
+}
+
+impl fmt::Debug for Linter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rules = self
+            .rules
+            .iter()
+            .map(|(rule, severity)| (rule.name(), *severity))
+            .collect::<Vec<_>>();
+        f.debug_struct("Linter").field("rules", &rules).finish()
+    }
+}
+
+impl Linter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    pub fn rule<R: LintRule + Send + Sync + 'static>(mut self, rule: R) -> Self {
+        let severity = rule.severity();
+        self.rules.push((Box::new(rule), severity));
+        self
+    }
+
+    // A misspelled name would leave the rule at its default without a word, so it is refused
+    pub fn severity(mut self, rule: impl AsRef<str>, severity: Severity) -> Self {
+        let name = rule.as_ref();
+        let mut found = false;
+        for (rule, current) in &mut self.rules {
+            if rule.name() == name {
+                *current = severity;
+                found = true;
+            }
+        }
+        assert!(
+            found,
+            "no lint rule is named `{name}`, add it before setting its severity"
+        );
+        self
+    }
+
+    pub fn run(&self, openapi: &OpenApi) -> LintReport {
+        let findings = self
+            .rules
+            .iter()
+            .filter(|(_, severity)| *severity != Severity::Off)
+            .flat_map(|(rule, severity)| {
+                rule.check(openapi)
+                    .into_iter()
+                    .map(move |violation| Finding {
+                        rule: rule.name().to_owned(),
+                        severity: *severity,
+                        location: violation.location,
+                        message: violation.message,
+                    })
+            })
+            .collect();
+        LintReport { findings }
+    }
+}
+
+pub fn lint(openapi: &OpenApi) -> LintReport {
+    Linter::new().run(openapi)
+}
+
+impl<S: Serialize> Scalar<S> {
+    // Meant for development builds, the findings are listed at the bottom of the page
+    pub fn with_lint_banner(mut self, report: LintReport) -> Self {
+        self.lint_banner = Some(report).filter(|report| !report.is_empty());
+        self
+    }
+
+    pub(crate) fn lint_banner_markup(&self) -> Markup {
+        html! {
+            @if let Some(report) = &self.lint_banner {
+                div style=(BANNER_STYLE) {
+                    strong { "OpenAPI lint: " (report.findings.len()) " finding(s)" }
+                    ul style="margin: 4px 0 0; padding-left: 20px;" {
+                        @for finding in report.findings() {
+                            li { (finding) }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn operations(openapi: &OpenApi) -> impl Iterator<Item = (String, &Operation)> {
+    openapi.paths.paths.iter().flat_map(|(path, item)| {
+        path_operations(item).map(move |(method, operation)| {
+            (format!("{} {path}", method.to_uppercase()), operation)
+        })
+    })
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OperationSummary;
+
+impl LintRule for OperationSummary {
+    fn name(&self) -> &str {
+        "operation-summary"
+    }
+
+    fn check(&self, openapi: &OpenApi) -> Vec<Violation> {
+        operations(openapi)
+            .filter(|(_, operation)| {
+                operation
+                    .summary
+                    .as_deref()
+                    .is_none_or(|summary| summary.trim().is_empty())
+            })
+            .map(|(location, _)| Violation::new(location, "operation has no summary"))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KebabCasePaths;
+
+impl LintRule for KebabCasePaths {
+    fn name(&self) -> &str {
+        "kebab-case-paths"
+    }
+
+    fn check(&self, openapi: &OpenApi) -> Vec<Violation> {
+        openapi
+            .paths
+            .paths
+            .keys()
+            .filter_map(|path| {
+                let segment = path
+                    .split('/')
+                    .filter(|segment| !segment.is_empty() && !segment.starts_with('{'))
+                    .find(|segment| !is_kebab_case(segment))?;
+                Some(Violation::new(
+                    path,
+                    format!("path segment `{segment}` is not kebab-case"),
+                ))
+            })
+            .collect()
+    }
+}
+
+fn is_kebab_case(segment: &str) -> bool {
+    segment.split('-').all(|word| {
+        !word.is_empty()
+            && word
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    })
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DeclaredTags;
+
+impl LintRule for DeclaredTags {
+    fn name(&self) -> &str {
+        "declared-tags"
+    }
+
+    fn check(&self, openapi: &OpenApi) -> Vec<Violation> {
+        let declared = openapi
+            .tags
+            .iter()
+            .flatten()
+            .map(|tag| tag.name.as_str())
+            .collect::<BTreeSet<_>>();
+        operations(openapi)
+            .flat_map(|(location, operation)| {
+                operation
+                    .tags
+                    .iter()
+                    .flatten()
+                    .filter(|tag| !declared.contains(tag.as_str()))
+                    .map(move |tag| {
+                        Violation::new(
+                            &location,
+                            format!("tag `{tag}` is not declared at the top level"),
+                        )
+                    })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestBodyExamples;
+
+impl LintRule for RequestBodyExamples {
+    fn name(&self) -> &str {
+        "request-body-examples"
+    }
+
+    fn check(&self, openapi: &OpenApi) -> Vec<Violation> {
+        let components = openapi.components.as_ref();
+        operations(openapi)
+            .flat_map(|(location, operation)| {
+                operation
+                    .request_body
+                    .iter()
+                    .flat_map(|body| &body.content)
+                    .filter(move |(_, content)| {
+                        let schema_example = content
+                            .schema
+                            .as_ref()
+                            .and_then(|schema| resolve_schema(schema, components))
+                            .is_some_and(has_example);
+                        content.example.is_none() && content.examples.is_empty() && !schema_example
+                    })
+                    .map(move |(media_type, _)| {
+                        Violation::new(
+                            &location,
+                            format!("request body `{media_type}` has no example"),
+                        )
+                    })
+            })
+            .collect()
+    }
+}
+
+#[allow(deprecated)]
+fn has_example(schema: &Schema) -> bool {
+    match schema {
+        Schema::Object(object) => !object.examples.is_empty() || object.example.is_some(),
+        Schema::Array(array) => !array.examples.is_empty(),
+        Schema::OneOf(one_of) => !one_of.examples.is_empty(),
+        Schema::AnyOf(any_of) => !any_of.examples.is_empty(),
+        Schema::AllOf(all_of) => !all_of.examples.is_empty(),
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ErrorResponseDescriptions;
+
+impl LintRule for ErrorResponseDescriptions {
+    fn name(&self) -> &str {
+        "error-response-descriptions"
+    }
+
+    fn check(&self, openapi: &OpenApi) -> Vec<Violation> {
+        operations(openapi)
+            .flat_map(|(location, operation)| {
+                operation
+                    .responses
+                    .responses
+                    .iter()
+                    .filter(|(status, _)| {
+                        status.starts_with('4') || status.starts_with('5') || *status == "default"
+                    })
+                    .filter_map(|(status, response)| match response {
+                        RefOr::T(response) if response.description.trim().is_empty() => {
+                            Some(status)
+                        }
+                        _ => None,
+                    })
+                    .map(move |status| {
+                        Violation::new(
+                            &location,
+                            format!("error response `{status}` has no description"),
+                        )
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    fn document(paths: Value) -> OpenApi {
+        serde_json::from_value(json!({
+            "openapi": "3.1.0",
+            "info": { "title": "Pets", "version": "1.0.0" },
+            "tags": [{ "name": "pets" }],
+            "paths": paths
+        }))
+        .unwrap()
+    }
+
+    fn operation(operation: Value) -> OpenApi {
+        document(json!({ "/pets": { "post": operation } }))
+    }
+
+    // Passes every built-in rule, the tests below break one thing at a time
+    fn clean() -> Value {
+        json!({
+            "summary": "Add a pet",
+            "tags": ["pets"],
+            "requestBody": {
+                "content": { "application/json": { "example": { "name": "Rex" } } }
+            },
+            "responses": {
+                "201": { "description": "" },
+                "404": { "description": "No such owner" }
+            }
+        })
+    }
+
+    fn with(field: &str, value: Value) -> Value {
+        let mut operation = clean();
+        operation[field] = value;
+        operation
+    }
+
+    fn violations(rule: impl LintRule, openapi: &OpenApi) -> Vec<(String, String)> {
+        rule.check(openapi)
+            .into_iter()
+            .map(|violation| (violation.location, violation.message))
+            .collect()
+    }
+
+    fn finding(severity: Severity) -> Finding {
+        Finding {
+            rule: "rule".to_owned(),
+            severity,
+            location: "GET /pets".to_owned(),
+            message: "message".to_owned(),
+        }
+    }
+
+    fn report(severities: &[Severity]) -> LintReport {
+        let findings = severities.iter().copied().map(finding).collect();
+        LintReport { findings }
+    }
+
+    #[test]
+    fn built_in_rules_pass_a_clean_document() {
+        let report = lint(&operation(clean()));
+        assert_eq!(report, LintReport::default());
+        assert!(report.is_clean());
+    }
+
+    #[test]
+    fn operations_need_a_summary() {
+        for summary in [Value::Null, json!(" ")] {
+            let openapi = operation(with("summary", summary));
+            let expected = [("POST /pets".into(), "operation has no summary".into())];
+            assert_eq!(violations(OperationSummary, &openapi), expected);
+        }
+        assert!(violations(OperationSummary, &operation(clean())).is_empty());
+    }
+
+    #[test]
+    fn path_segments_are_kebab_case() {
+        let openapi = document(json!({
+            "/pet-owners/{ownerId}/pets": {},
+            "/petOwners": {},
+            "/pet_owners": {},
+            "/pets-": {}
+        }));
+        let expected = [
+            ("/petOwners", "`petOwners`"),
+            ("/pet_owners", "`pet_owners`"),
+            ("/pets-", "`pets-`"),
+        ]
+        .map(|(path, segment)| {
+            let message = format!("path segment {segment} is not kebab-case");
+            (path.to_owned(), message)
+        });
+        assert_eq!(violations(KebabCasePaths, &openapi), expected);
+    }
+
+    #[test]
+    fn tags_are_declared_at_the_top_level() {
+        let openapi = operation(with("tags", json!(["pets", "owners"])));
+        let message = "tag `owners` is not declared at the top level";
+        let expected = [("POST /pets".into(), message.into())];
+        assert_eq!(violations(DeclaredTags, &openapi), expected);
+        assert!(violations(DeclaredTags, &operation(clean())).is_empty());
+    }
+
+    #[test]
+    fn request_bodies_have_an_example() {
+        let body = json!({ "content": { "application/json": { "schema": { "type": "object" } } } });
+        let openapi = operation(with("requestBody", body));
+        let message = "request body `application/json` has no example";
+        let expected = [("POST /pets".into(), message.into())];
+        assert_eq!(violations(RequestBodyExamples, &openapi), expected);
+
+        // An example on the schema behind a reference counts as well
+        let mut openapi = operation(with(
+            "requestBody",
+            json!({
+                "content": {
+                    "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } }
+                }
+            }),
+        ));
+        let pet = json!({ "type": "object", "examples": [{ "name": "Rex" }] });
+        openapi.components = serde_json::from_value(json!({ "schemas": { "Pet": pet } })).unwrap();
+        assert!(violations(RequestBodyExamples, &openapi).is_empty());
+    }
+
+    #[test]
+    fn error_responses_are_described() {
+        let responses = json!({
+            "201": { "description": "" },
+            "404": { "description": "" },
+            "500": { "description": "Storage failed" },
+            "default": { "description": " " }
+        });
+        let openapi = operation(with("responses", responses));
+        let expected = ["404", "default"].map(|status| {
+            let message = format!("error response `{status}` has no description");
+            ("POST /pets".to_owned(), message)
+        });
+        assert_eq!(violations(ErrorResponseDescriptions, &openapi), expected);
+        assert!(violations(ErrorResponseDescriptions, &operation(clean())).is_empty());
+    }
+
+    #[test]
+    fn severities_are_overridden_per_rule() {
+        let openapi = operation(with("summary", Value::Null));
+        let findings = Linter::new().run(&openapi).findings().to_vec();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule(), "operation-summary");
+        assert_eq!(findings[0].severity(), Severity::Warning);
+        assert_eq!(findings[0].location(), "POST /pets");
+
+        let linter = Linter::new().severity("operation-summary", Severity::Error);
+        let report = linter.run(&openapi);
+        assert_eq!(report.findings()[0].severity(), Severity::Error);
+        assert!(report.has_errors());
+
+        let linter = Linter::new().severity("operation-summary", Severity::Off);
+        assert!(linter.run(&openapi).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "no lint rule is named `operation-summaries`")]
+    fn unknown_rules_are_refused() {
+        let _ = Linter::new().severity("operation-summaries", Severity::Off);
+    }
+
+    #[test]
+    fn info_findings_keep_a_report_clean() {
+        assert!(report(&[]).is_clean());
+        assert!(report(&[Severity::Info]).is_clean());
+        assert!(!report(&[Severity::Info, Severity::Warning]).is_clean());
+        assert!(!report(&[Severity::Warning]).has_errors());
+        assert!(report(&[Severity::Warning, Severity::Error]).has_errors());
+    }
+
+    #[test]
+    fn banner_lists_the_findings() {
+        let scalar = Scalar::new(json!({})).with_lint_banner(report(&[Severity::Warning]));
+        let banner = scalar.lint_banner_markup().into_string();
+        assert!(banner.contains("OpenAPI lint: 1 finding(s)"));
+        assert!(banner.contains("warning [rule] GET /pets: message"));
+        assert!(
+            scalar
+                .render("openapi.json", "scalar.js")
+                .into_string()
+                .contains("OpenAPI lint: 1 finding(s)")
+        );
+
+        // An empty report leaves the page as it is
+        let scalar = Scalar::new(json!({})).with_lint_banner(LintReport::default());
+        assert!(scalar.lint_banner_markup().into_string().is_empty());
+        assert!(
+            !scalar
+                .render("openapi.json", "scalar.js")
+                .into_string()
+                .contains("OpenAPI lint")
+        );
+    }
+}
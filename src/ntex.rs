@@ -0,0 +1,96 @@
+use std::rc::Rc;
+
+use ntex::http::StatusCode;
+use ntex::util::Bytes;
+use ntex::web::dev::{WebServiceConfig, WebServiceFactory};
+use ntex::web::{self, ErrorRenderer, HttpRequest, HttpResponse};
+
+use crate::service::{ScalarService, to_request};
+use crate::{Scalar, Serialize};
+
+impl<S: Serialize, Err: ErrorRenderer> WebServiceFactory<Err> for Scalar<S> {
+    fn register(self, config: &mut WebServiceConfig<Err>) {
+        let service = self.into_service();
+        for path in service.paths() {
+            let resource = Rc::new(ScalarResource {
+                service: service.clone(),
+                path,
+            });
+            // A `State` extractor would tie the impl to the error renderers that can report it
+            web::resource(resource.path.as_str())
+                .to(move |request: HttpRequest| {
+                    let resource = resource.clone();
+                    async move { serve_scalar(&request, &resource) }
+                })
+                .register(config);
+        }
+    }
+}
+
+struct ScalarResource {
+    service: ScalarService,
+    path: String,
+}
+
+// Like Actix-web, the resource path tells which part of the original uri belongs to a scope
+fn serve_scalar(request: &HttpRequest, resource: &ScalarResource) -> HttpResponse {
+    let uri = request.uri().to_string();
+    let authority = request
+        .uri()
+        .authority()
+        .map(|authority| authority.as_str());
+    let headers = request
+        .headers()
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_bytes()));
+    let method = request.method().as_str();
+    let response = to_request(method, &uri, &resource.path, authority, headers)
+        .and_then(|request| resource.service.handle(&request));
+    let Some(response) = response else {
+        return HttpResponse::NotFound().finish();
+    };
+    let mut builder =
+        HttpResponse::build(StatusCode::from_u16(response.status().as_u16()).unwrap());
+    for (name, value) in response.headers() {
+        if name != http::header::CONTENT_LENGTH {
+            builder.header(name.as_str(), value.as_bytes());
+        }
+    }
+    // ntex has a bytes type of its own, so the shared body is copied once per response
+    builder.body(Bytes::copy_from_slice(response.body()))
+}
+
+#[cfg(test)]
+mod tests {
+    use ntex::http::StatusCode;
+    use ntex::web::{App, test};
+
+    use crate::Scalar;
+
+    #[ntex::test]
+    async fn serves_the_docs_routes() {
+        let scalar = Scalar::new(serde_json::json!({})).with_url("/scalar");
+        let app = test::init_service(App::new().service(scalar)).await;
+
+        let request = test::TestRequest::get().uri("/scalar").to_request();
+        let page = test::call_service(&app, request).await;
+        assert_eq!(page.status(), StatusCode::OK);
+
+        let request = test::TestRequest::get()
+            .uri("/scalar/api-docs/openapi.json")
+            .to_request();
+        let spec = test::call_service(&app, request).await;
+        assert_eq!(test::read_body(spec).await, "{}");
+
+        let request = test::TestRequest::default()
+            .method(ntex::http::Method::HEAD)
+            .uri("/scalar")
+            .to_request();
+        let head = test::call_service(&app, request).await;
+        assert_eq!(head.status(), StatusCode::OK);
+
+        let request = test::TestRequest::get().uri("/other").to_request();
+        let other = test::call_service(&app, request).await;
+        assert_eq!(other.status(), StatusCode::NOT_FOUND);
+    }
+}
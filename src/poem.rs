@@ -0,0 +1,74 @@
+use poem::error::NotFoundError;
+use poem::{Body, Endpoint, Request, Response, Result, Route};
+
+use crate::service::{Nested, ScalarService};
+use crate::{Scalar, Serialize};
+
+impl<S: Serialize> From<Scalar<S>> for Route {
+    fn from(scalar: Scalar<S>) -> Route {
+        let service = scalar.into_service();
+        service
+            .paths()
+            .into_iter()
+            .fold(Route::new(), |route, path| route.at(path, service.clone()))
+    }
+}
+
+impl Endpoint for ScalarService {
+    type Output = Response;
+
+    async fn call(&self, request: Request) -> Result<Self::Output> {
+        let mut http_request = http::Request::new(());
+        *http_request.method_mut() = request.method().clone();
+        *http_request.uri_mut() = request.uri().clone();
+        *http_request.headers_mut() = request.headers().clone();
+        // `Route::nest` strips its prefix from the uri, and keeps the original one aside
+        if request.original_uri().path() != request.uri().path() {
+            http_request.extensions_mut().insert(Nested);
+        }
+        let Some(response) = self.handle(&http_request) else {
+            return Err(NotFoundError.into());
+        };
+        let (parts, body) = response.into_parts();
+        let mut response = Response::from(Body::from(body));
+        response.set_status(parts.status);
+        *response.headers_mut() = parts.headers;
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use poem::http::{StatusCode, uri::Scheme};
+    use poem::web::{LocalAddr, RemoteAddr};
+    use poem::{Body, Endpoint, Request, RequestParts, Route};
+
+    use crate::Scalar;
+
+    // Built the way the server builds it, which is what sets the original uri
+    fn request(uri: &str) -> Request {
+        let (parts, _) = http::Request::get(uri).body(()).unwrap().into_parts();
+        let parts = (
+            parts,
+            LocalAddr::default(),
+            RemoteAddr::default(),
+            Scheme::HTTP,
+        );
+        Request::from_parts(RequestParts::from(parts), Body::empty())
+    }
+
+    #[tokio::test]
+    async fn nested_endpoint_sees_paths_below_its_mount_point() {
+        let service = Scalar::new(serde_json::json!({}))
+            .with_url("/scalar")
+            .into_service();
+        let route = Route::new().nest("/scalar", service.clone());
+        for uri in ["/scalar", "/scalar/api-docs/openapi.json"] {
+            let status = route.get_response(request(uri)).await.status();
+            assert_eq!(status, StatusCode::OK);
+        }
+        let route = Route::new().at("/", service);
+        let status = route.get_response(request("/")).await.status();
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::service::ScalarService;
+
+// Scopes with dynamic segments give one prefix per value, only so many pages are kept rendered
+const MAX_REBASED: usize = 64;
+
+// Pages rebased below the prefixes a service was reached through, for frameworks that do not tell
+// where a route is registered. Any client can make up prefixes, so the least recently used page
+// makes room for a new one instead of the first ones being kept forever
+#[derive(Debug)]
+pub(crate) struct Rebased {
+    service: ScalarService,
+    pages: Mutex<RebasedPages>,
+}
+
+#[derive(Debug, Default)]
+struct RebasedPages {
+    clock: u64,
+    services: HashMap<String, (ScalarService, u64)>,
+}
+
+impl Rebased {
+    pub(crate) fn new(service: ScalarService) -> Self {
+        Self {
+            service,
+            pages: Mutex::new(RebasedPages::default()),
+        }
+    }
+
+    pub(crate) fn service(&self, prefix: &str) -> ScalarService {
+        if prefix.is_empty() {
+            return self.service.clone();
+        }
+        {
+            let mut pages = self.pages.lock().unwrap();
+            pages.clock += 1;
+            let clock = pages.clock;
+            if let Some((service, used)) = pages.services.get_mut(prefix) {
+                *used = clock;
+                return service.clone();
+            }
+        }
+        // Rendering is left outside of the lock, other prefixes are still answered meanwhile
+        let service = self.service.rebased(prefix);
+        let mut pages = self.pages.lock().unwrap();
+        if pages.services.len() >= MAX_REBASED {
+            let oldest = pages
+                .services
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(prefix, _)| prefix.clone());
+            if let Some(oldest) = oldest {
+                pages.services.remove(&oldest);
+            }
+        }
+        pages.clock += 1;
+        let clock = pages.clock;
+        pages
+            .services
+            .insert(prefix.to_owned(), (service.clone(), clock));
+        service
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Scalar;
+
+    fn cached(rebased: &Rebased, prefix: &str) -> bool {
+        rebased.pages.lock().unwrap().services.contains_key(prefix)
+    }
+
+    #[test]
+    fn least_recently_used_pages_make_room() {
+        let rebased = Rebased::new(Scalar::new(serde_json::json!({})).into_service());
+        for index in 0..MAX_REBASED {
+            rebased.service(&format!("/tenant-{index}"));
+        }
+        rebased.service("/tenant-0");
+        rebased.service("/junk");
+        assert_eq!(rebased.pages.lock().unwrap().services.len(), MAX_REBASED);
+        assert!(cached(&rebased, "/tenant-0"));
+        assert!(cached(&rebased, "/junk"));
+        assert!(!cached(&rebased, "/tenant-1"));
+        // The service that is not below any prefix is never cached
+        rebased.service("");
+        assert!(!cached(&rebased, ""));
+    }
+}
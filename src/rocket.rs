@@ -1,59 +1,184 @@
-use rocket::http::Method;
-use rocket::response::content::{RawHtml, RawJavaScript, RawJson};
+use std::io::Cursor;
+use std::net::SocketAddr;
+
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::http::{Method, Status};
 use rocket::route::{Handler, Outcome};
-use rocket::{Data, Request, Route};
+use rocket::{Build, Config, Data, Orbit, Request, Response, Rocket, Route};
 
-use crate::{SCALAR_API_REFERENCE_JS, Scalar, Serialize};
+use crate::service::{ScalarService, to_request};
+use crate::{Scalar, Serialize};
 
 impl<S: Serialize> From<Scalar<S>> for Vec<Route> {
     fn from(scalar: Scalar<S>) -> Self {
-        let markup = scalar.markup();
-        let scalar_url = scalar.url.as_ref();
-        let script_url = scalar.script_url();
-        let api_json = scalar.api_json();
-        let api_json_url = scalar.api_json_url();
-        vec![
-            Route::new(Method::Get, scalar_url, ScalarHandler(markup.0)),
-            Route::new(
-                Method::Get,
-                script_url.as_ref(),
-                ScalarScriptHandler(SCALAR_API_REFERENCE_JS.to_owned()),
-            ),
-            Route::new(
-                Method::Get,
-                api_json_url.as_ref(),
-                ScalarApiJsonHandler(api_json),
-            ),
-        ]
+        routes(&scalar.into_service())
     }
 }
 
-#[derive(Clone)]
-struct ScalarHandler(String);
+fn routes(service: &ScalarService) -> Vec<Route> {
+    service
+        .paths()
+        .into_iter()
+        .flat_map(|path| {
+            let handler = ScalarHandler {
+                service: service.clone(),
+                path: path.clone(),
+            };
+            // HEAD requests are answered by the GET route, rocket drops the body itself
+            [
+                Route::new(Method::Get, &path, handler.clone()),
+                Route::new(Method::Options, &path, handler),
+            ]
+        })
+        .collect()
+}
+
+// Mounts the routes itself, so it knows the base and can point the page at the prefixed urls
+pub struct ScalarFairing {
+    base: String,
+    service: ScalarService,
+}
+
+impl ScalarFairing {
+    pub fn new<S: Serialize>(base: impl AsRef<str>, scalar: Scalar<S>) -> Self {
+        let base = base.as_ref().to_owned();
+        let service = ScalarService::mounted_at(scalar, &base);
+        Self { base, service }
+    }
+
+    fn docs_path(&self) -> String {
+        let path = format!("{}{}", self.base.trim_end_matches('/'), self.service.url());
+        match path.trim_end_matches('/') {
+            "" => "/".to_owned(),
+            path => path.to_owned(),
+        }
+    }
+
+    fn docs_url(&self, config: &Config) -> String {
+        let scheme = if config.tls_enabled() {
+            "https"
+        } else {
+            "http"
+        };
+        // A socket address brackets an IPv6 address, the way a url needs it
+        let address = SocketAddr::new(config.address, config.port);
+        format!("{scheme}://{address}{}", self.docs_path())
+    }
+}
 
 #[rocket::async_trait]
-impl Handler for ScalarHandler {
-    async fn handle<'r>(&self, request: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
-        Outcome::from(request, RawHtml(self.0.clone()))
+impl Fairing for ScalarFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Scalar",
+            kind: Kind::Ignite | Kind::Liftoff,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        Ok(rocket.mount(self.base.as_str(), routes(&self.service)))
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        // Goes through the logger rocket installs, which applies the configured log level
+        log::info!("Scalar documentation at {}", self.docs_url(rocket.config()));
     }
 }
 
+// The route path tells the service which part of the original uri belongs to the mount point
 #[derive(Clone)]
-struct ScalarScriptHandler(String);
+struct ScalarHandler {
+    service: ScalarService,
+    path: String,
+}
 
 #[rocket::async_trait]
-impl Handler for ScalarScriptHandler {
-    async fn handle<'r>(&self, request: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
-        Outcome::from(request, RawJavaScript(self.0.clone()))
+impl Handler for ScalarHandler {
+    async fn handle<'r>(&self, request: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let headers = request.headers().iter().collect::<Vec<_>>();
+        let headers = headers
+            .iter()
+            .map(|header| (header.name.as_str(), header.value.as_bytes()));
+        let uri = request.uri().to_string();
+        let authority = request.host().map(|host| host.to_string());
+        let method = request.method().as_str();
+        let response = to_request(method, &uri, &self.path, authority.as_deref(), headers)
+            .and_then(|request| self.service.handle(&request));
+        let Some(response) = response else {
+            return Outcome::forward(data, Status::NotFound);
+        };
+        let mut builder = Response::build();
+        builder.status(Status::new(response.status().as_u16()));
+        for (name, value) in response.headers() {
+            if name == http::header::CONTENT_LENGTH {
+                continue;
+            }
+            if let Ok(value) = value.to_str() {
+                builder.raw_header_adjoin(name.as_str().to_owned(), value.to_owned());
+            }
+        }
+        let body = response.into_body();
+        builder.sized_body(body.len(), Cursor::new(body));
+        Outcome::Success(builder.finalize())
     }
 }
 
-#[derive(Clone)]
-struct ScalarApiJsonHandler(String);
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv6Addr;
 
-#[rocket::async_trait]
-impl Handler for ScalarApiJsonHandler {
-    async fn handle<'r>(&self, request: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
-        Outcome::from(request, RawJson(self.0.clone()))
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+
+    use super::*;
+    use crate::{Tenant, TenantResolver};
+
+    struct HostTenants;
+
+    impl TenantResolver for HostTenants {
+        fn resolve(&self, tenant_id: &str) -> Option<Tenant> {
+            let openapi = serde_json::json!({ "tenant": tenant_id });
+            (tenant_id == "acme.example.com").then(|| Tenant::new(Scalar::new(openapi)))
+        }
+    }
+
+    #[test]
+    fn docs_url_brackets_ipv6_addresses() {
+        let fairing = ScalarFairing::new(
+            "/docs",
+            Scalar::new(serde_json::json!({})).with_url("/scalar"),
+        );
+        let config = Config {
+            address: Ipv6Addr::LOCALHOST.into(),
+            ..Config::debug_default()
+        };
+        assert_eq!(fairing.docs_url(&config), "http://[::1]:8000/docs/scalar");
+        let config = Config::debug_default();
+        assert_eq!(
+            fairing.docs_url(&config),
+            "http://127.0.0.1:8000/docs/scalar"
+        );
+    }
+
+    #[test]
+    fn tenants_are_served_below_the_mount_point() {
+        let scalar = Scalar::new(serde_json::json!({}))
+            .with_url("/scalar")
+            .with_tenants(HostTenants);
+        let rocket = rocket::build().mount("/docs", Vec::<Route>::from(scalar));
+        let client = Client::untracked(rocket).unwrap();
+        let spec = |host: &'static str| {
+            client
+                .get("/docs/scalar/api-docs/openapi.json")
+                .header(Header::new("Host", host))
+                .dispatch()
+        };
+        let response = spec("acme.example.com:8000");
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(
+            response.into_string().unwrap(),
+            r#"{"tenant":"acme.example.com"}"#
+        );
+        assert_eq!(spec("globex.example.com").status(), Status::NotFound);
     }
 }
@@ -0,0 +1,452 @@
+use std::fmt::{Debug, Formatter, Write};
+use std::sync::Arc;
+
+use serde_json::Value;
+use utoipa::Modify;
+use utoipa::openapi::path::{Parameter, ParameterIn};
+use utoipa::openapi::{Components, OpenApi, RefOr, Server};
+
+use crate::extensions::{CODE_SAMPLE_KEY, CodeSample, append_extension};
+use crate::spec::{example_for_schema, path_operations_mut};
+
+const X_CODE_SAMPLES: &str = "x-codeSamples";
+const DEFAULT_BASE_URL: &str = "http://localhost";
+
+// Strict and reserved keywords of every edition, none of them can be bound with `let`
+const KEYWORDS: [&str; 52] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "union", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+type Template = Arc<dyn Fn(&RustSample) -> String + Send + Sync>;
+
+#[derive(Clone, Default)]
+pub struct RustCodeSamples {
+    base_url: Option<String>,
+    label: Option<String>,
+    template: Option<Template>,
+}
+
+impl RustCodeSamples {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn base_url(mut self, base_url: impl AsRef<str>) -> Self {
+        self.base_url = Some(base_url.as_ref().trim_end_matches('/').to_owned());
+        self
+    }
+
+    pub fn label(mut self, label: impl AsRef<str>) -> Self {
+        self.label = Some(label.as_ref().to_owned());
+        self
+    }
+
+    pub fn template<F>(mut self, template: F) -> Self
+    where
+        F: Fn(&RustSample) -> String + Send + Sync + 'static,
+    {
+        self.template = Some(Arc::new(template));
+        self
+    }
+
+    fn render(&self, sample: &RustSample) -> String {
+        match self.template.as_ref() {
+            Some(template) => template(sample),
+            None => sample.render(),
+        }
+    }
+}
+
+impl Debug for RustCodeSamples {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RustCodeSamples")
+            .field("base_url", &self.base_url)
+            .field("label", &self.label)
+            .field("template", &self.template.is_some())
+            .finish()
+    }
+}
+
+impl Modify for RustCodeSamples {
+    fn modify(&self, openapi: &mut OpenApi) {
+        // The servers declared in the document are the best guess for a copy-pasteable url
+        let base_url = self.base_url.clone().unwrap_or_else(|| {
+            openapi
+                .servers
+                .as_ref()
+                .and_then(|servers| servers.first())
+                .map(server_url)
+                .unwrap_or_else(|| DEFAULT_BASE_URL.to_owned())
+        });
+        let label = self.label.as_deref().unwrap_or("Rust");
+        let components = openapi.components.as_ref();
+        for (path, item) in openapi.paths.paths.iter_mut() {
+            let shared_parameters = item.parameters.clone().unwrap_or_default();
+            for (method, operation) in path_operations_mut(item) {
+                let mut sample = RustSample {
+                    method: method.to_owned(),
+                    url: format!("{base_url}{path}"),
+                    path_params: Vec::new(),
+                    query_params: Vec::new(),
+                    header_params: Vec::new(),
+                    body: None,
+                };
+                // A parameter of the operation overrides the one of the path item with the same
+                // name and location
+                let operation_parameters = operation.parameters.iter().flatten();
+                let parameters = shared_parameters
+                    .iter()
+                    .filter(|shared| {
+                        !operation_parameters.clone().any(|parameter| {
+                            parameter.name == shared.name
+                                && parameter.parameter_in == shared.parameter_in
+                        })
+                    })
+                    .chain(operation_parameters.clone());
+                let mut idents = Vec::new();
+                for parameter in parameters {
+                    let param = SampleParam {
+                        name: parameter.name.clone(),
+                        ident: unique_ident(parameter, &mut idents),
+                        example: parameter_example(parameter, components),
+                    };
+                    match parameter.parameter_in {
+                        ParameterIn::Path => sample.path_params.push(param),
+                        ParameterIn::Query => sample.query_params.push(param),
+                        ParameterIn::Header => sample.header_params.push(param),
+                        ParameterIn::Cookie => {}
+                    }
+                }
+                sample.body = operation
+                    .request_body
+                    .as_ref()
+                    .and_then(|body| {
+                        body.content
+                            .iter()
+                            .find(|(content_type, _)| content_type.contains("json"))
+                    })
+                    .map(|(_, content)| {
+                        let example = content.examples.values().find_map(|example| match example {
+                            RefOr::T(example) => example.value.clone(),
+                            RefOr::Ref(_) => None,
+                        });
+                        content
+                            .example
+                            .clone()
+                            .or(example)
+                            .or_else(|| {
+                                content
+                                    .schema
+                                    .as_ref()
+                                    .map(|schema| example_for_schema(schema, components))
+                            })
+                            .unwrap_or(Value::Null)
+                    });
+                let sample = CodeSample::new("rust", self.render(&sample)).label(label);
+                append_extension(
+                    &mut operation.extensions,
+                    X_CODE_SAMPLES,
+                    vec![serde_json::to_value(sample).unwrap()],
+                    CODE_SAMPLE_KEY,
+                );
+            }
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct RustSample {
+    pub method: String,
+    pub url: String,
+    pub path_params: Vec<SampleParam>,
+    pub query_params: Vec<SampleParam>,
+    pub header_params: Vec<SampleParam>,
+    pub body: Option<Value>,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct SampleParam {
+    pub name: String,
+    pub ident: String,
+    pub example: Value,
+}
+
+impl RustSample {
+    pub fn render(&self) -> String {
+        let mut code = String::new();
+        let params = self
+            .path_params
+            .iter()
+            .chain(&self.query_params)
+            .chain(&self.header_params);
+        for param in params {
+            writeln!(
+                code,
+                "let {} = {};",
+                param.ident,
+                to_literal(&param.example)
+            )
+            .unwrap();
+        }
+        code.push_str("let client = reqwest::Client::new();\n");
+        code.push_str("let response = client\n");
+        let url = if self.path_params.is_empty() {
+            format!("{:?}", self.url)
+        } else {
+            // Braces other than the path parameters are written out, e.g. of a templated base url
+            let url = self.url.replace('{', "{{").replace('}', "}}");
+            let url = self.path_params.iter().fold(url, |url, param| {
+                url.replace(
+                    &format!("{{{{{}}}}}", param.name),
+                    &format!("{{{}}}", param.ident),
+                )
+            });
+            format!("format!({url:?})")
+        };
+        match self.method.as_str() {
+            "get" | "put" | "post" | "delete" | "patch" | "head" => {
+                writeln!(code, "    .{}({url})", self.method).unwrap();
+            }
+            method => {
+                let method = method.to_uppercase();
+                writeln!(code, "    .request(reqwest::Method::{method}, {url})").unwrap();
+            }
+        }
+        if !self.query_params.is_empty() {
+            let query = self
+                .query_params
+                .iter()
+                .map(|param| format!("({:?}, {}.to_string())", param.name, param.ident))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(code, "    .query(&[{query}])").unwrap();
+        }
+        for param in &self.header_params {
+            writeln!(
+                code,
+                "    .header({:?}, {}.to_string())",
+                param.name, param.ident
+            )
+            .unwrap();
+        }
+        if let Some(body) = self.body.as_ref() {
+            let body = serde_json::to_string_pretty(body)
+                .unwrap()
+                .replace('\n', "\n    ");
+            writeln!(code, "    .json(&serde_json::json!({body}))").unwrap();
+        }
+        code.push_str("    .send()\n");
+        code.push_str("    .await?;\n");
+        code.push_str("println!(\"{}\", response.status());\n");
+        code
+    }
+}
+
+// A server url is a template, its variables are filled with their defaults
+fn server_url(server: &Server) -> String {
+    let url = server
+        .variables
+        .iter()
+        .flatten()
+        .fold(server.url.clone(), |url, (name, variable)| {
+            url.replace(&format!("{{{name}}}"), &variable.default_value)
+        });
+    url.trim_end_matches('/').to_owned()
+}
+
+fn parameter_example(parameter: &Parameter, components: Option<&Components>) -> Value {
+    // The example of a parameter is not exposed by utoipa, but it is part of the serialized form
+    let example = serde_json::to_value(parameter)
+        .ok()
+        .and_then(|mut value| value.get_mut("example").map(Value::take));
+    example
+        .or_else(|| {
+            parameter
+                .schema
+                .as_ref()
+                .map(|schema| example_for_schema(schema, components))
+        })
+        .unwrap_or(Value::Null)
+}
+
+// The same name may be used in several locations, e.g. a path `id` and a query `id`, the later
+// ones are told apart by their location
+fn unique_ident(parameter: &Parameter, idents: &mut Vec<String>) -> String {
+    let base = to_ident(&parameter.name);
+    let location = match parameter.parameter_in {
+        ParameterIn::Path => "path",
+        ParameterIn::Query => "query",
+        ParameterIn::Header => "header",
+        ParameterIn::Cookie => "cookie",
+    };
+    let mut ident = base.clone();
+    // A keyword keeps its suffix only as long as it is needed
+    let stem = base.trim_end_matches('_');
+    let mut counter = 1;
+    while idents.contains(&ident) {
+        ident = match counter {
+            1 => format!("{stem}_{location}"),
+            counter => format!("{stem}_{location}_{counter}"),
+        };
+        counter += 1;
+    }
+    idents.push(ident.clone());
+    ident
+}
+
+fn to_ident(name: &str) -> String {
+    let mut ident = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    // A lone `_` cannot be read back, a keyword is suffixed instead of made raw, which would not
+    // work for `self` and would not read well inside `format!`
+    if ident.bytes().all(|byte| byte == b'_') {
+        ident.push_str("param");
+    } else if KEYWORDS.contains(&ident.as_str()) {
+        ident.push('_');
+    }
+    ident
+}
+
+fn to_literal(value: &Value) -> String {
+    match value {
+        Value::String(value) => format!("{value:?}"),
+        Value::Number(value) => value.to_string(),
+        Value::Bool(value) => value.to_string(),
+        Value::Null => "\"\"".to_owned(),
+        value => format!("serde_json::json!({value})"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn sample(openapi: Value) -> String {
+        sample_with(RustCodeSamples::new(), openapi)
+    }
+
+    fn sample_with(samples: RustCodeSamples, openapi: Value) -> String {
+        let mut openapi: OpenApi = serde_json::from_value(openapi).unwrap();
+        samples.modify(&mut openapi);
+        let operation = openapi.paths.paths["/items/{type}"].get.as_ref().unwrap();
+        let samples = &operation.extensions.as_ref().unwrap()[X_CODE_SAMPLES];
+        samples[0]["source"].as_str().unwrap().to_owned()
+    }
+
+    #[test]
+    fn keywords_are_suffixed() {
+        for (name, ident) in [
+            ("type", "type_"),
+            ("ref", "ref_"),
+            ("match", "match_"),
+            ("self", "self_"),
+            ("async", "async_"),
+            ("_", "_param"),
+            ("1st", "_1st"),
+            ("X-Request-Id", "x_request_id"),
+        ] {
+            assert_eq!(to_ident(name), ident);
+        }
+    }
+
+    #[test]
+    fn operation_parameters_override_path_parameters() {
+        let source = sample(json!({
+            "openapi": "3.1.0",
+            "info": { "title": "items", "version": "1" },
+            "paths": {
+                "/items/{type}": {
+                    "parameters": [
+                        { "name": "type", "in": "path", "required": true, "example": "shared" },
+                        { "name": "match", "in": "query", "required": false, "example": "all" }
+                    ],
+                    "get": {
+                        "parameters": [
+                            { "name": "type", "in": "path", "required": true, "example": "book" }
+                        ],
+                        "responses": {}
+                    }
+                }
+            }
+        }));
+        assert_eq!(source.matches("let type_ = ").count(), 1);
+        assert!(source.contains("let type_ = \"book\";"));
+        assert!(source.contains("let match_ = \"all\";"));
+        assert!(source.contains("format!(\"http://localhost/items/{type_}\")"));
+        assert!(source.contains(".query(&[(\"match\", match_.to_string())])"));
+    }
+
+    #[test]
+    fn idents_are_unique_across_locations() {
+        let source = sample(json!({
+            "openapi": "3.1.0",
+            "info": { "title": "items", "version": "1" },
+            "paths": {
+                "/items/{type}": {
+                    "get": {
+                        "parameters": [
+                            { "name": "type", "in": "path", "required": true, "example": 5 },
+                            { "name": "type", "in": "query", "required": false, "example": "x" },
+                            { "name": "Type", "in": "header", "required": false, "example": "y" }
+                        ],
+                        "responses": {}
+                    }
+                }
+            }
+        }));
+        assert!(source.contains("let type_ = 5;"));
+        assert!(source.contains("let type_query = \"x\";"));
+        assert!(source.contains("let type_header = \"y\";"));
+        assert!(source.contains("format!(\"http://localhost/items/{type_}\")"));
+        assert!(source.contains(".query(&[(\"type\", type_query.to_string())])"));
+        assert!(source.contains(".header(\"Type\", type_header.to_string())"));
+    }
+
+    #[test]
+    fn server_variables_are_filled_with_their_defaults() {
+        let openapi = json!({
+            "openapi": "3.1.0",
+            "info": { "title": "items", "version": "1" },
+            "servers": [{
+                "url": "https://{region}.example.com/{version}/",
+                "variables": {
+                    "region": { "default": "eu" },
+                    "version": { "default": "v1" }
+                }
+            }],
+            "paths": {
+                "/items/{type}": {
+                    "get": {
+                        "parameters": [{ "name": "type", "in": "path", "required": true }],
+                        "responses": {}
+                    }
+                }
+            }
+        });
+        let source = sample(openapi.clone());
+        assert!(source.contains("format!(\"https://eu.example.com/v1/items/{type_}\")"));
+        // Braces that are not path parameters are escaped, so the sample still compiles
+        let samples = RustCodeSamples::new().base_url("https://{tenant}.example.com");
+        let source = sample_with(samples, openapi);
+        assert!(source.contains("format!(\"https://{{tenant}}.example.com/items/{type_}\")"));
+    }
+}
@@ -0,0 +1,123 @@
+use salvo::http::StatusCode;
+use salvo::{Depot, FlowCtrl, Handler, Request, Response, Router, async_trait};
+
+use crate::rebased::Rebased;
+use crate::service::to_request;
+use crate::{Scalar, Serialize};
+
+impl<S: Serialize> From<Scalar<S>> for Router {
+    fn from(scalar: Scalar<S>) -> Router {
+        let service = scalar.into_service();
+        service
+            .paths()
+            .into_iter()
+            .fold(Router::new(), |router, path| {
+                let handler = ScalarHandler {
+                    service: Rebased::new(service.clone()),
+                    path: path.clone(),
+                };
+                router.push(Router::with_path(path).goal(handler))
+            })
+    }
+}
+
+// Like Actix-web, the router does not tell where it is pushed, but the matched path does: it is the
+// path of the enclosing routers followed by the path of the route
+struct ScalarHandler {
+    service: Rebased,
+    path: String,
+}
+
+#[async_trait]
+impl Handler for ScalarHandler {
+    async fn handle(
+        &self,
+        request: &mut Request,
+        _depot: &mut Depot,
+        response: &mut Response,
+        _ctrl: &mut FlowCtrl,
+    ) {
+        let prefix = request.uri().path().strip_suffix(self.path.as_str());
+        let service = self.service.service(prefix.unwrap_or_default());
+        let uri = request.uri().to_string();
+        let authority = request
+            .uri()
+            .authority()
+            .map(|authority| authority.as_str());
+        let headers = request
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_bytes()));
+        let method = request.method().as_str();
+        let scalar_response = to_request(method, &uri, &self.path, authority, headers)
+            .and_then(|request| service.handle(&request));
+        let Some(scalar_response) = scalar_response else {
+            response.status_code(StatusCode::NOT_FOUND);
+            return;
+        };
+        let (parts, body) = scalar_response.into_parts();
+        response.status_code(parts.status);
+        response.set_headers(parts.headers);
+        response.body(body);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo::test::{ResponseExt, TestClient};
+    use salvo::{Router, Service};
+
+    use crate::Scalar;
+
+    #[tokio::test]
+    async fn serves_the_docs_routes() {
+        let router = Router::from(Scalar::new(serde_json::json!({})).with_url("/scalar"));
+        let service = Service::new(router);
+
+        let mut page = TestClient::get("http://localhost/scalar")
+            .send(&service)
+            .await;
+        assert_eq!(page.status_code, Some(salvo::http::StatusCode::OK));
+        assert!(
+            page.take_string()
+                .await
+                .unwrap()
+                .contains("api-docs/openapi.json")
+        );
+
+        let mut spec = TestClient::get("http://localhost/scalar/api-docs/openapi.json")
+            .send(&service)
+            .await;
+        assert_eq!(spec.take_string().await.unwrap(), "{}");
+
+        let other = TestClient::get("http://localhost/other")
+            .send(&service)
+            .await;
+        assert_eq!(other.status_code, Some(salvo::http::StatusCode::NOT_FOUND));
+    }
+
+    #[tokio::test]
+    async fn serves_the_docs_routes_below_a_nested_router() {
+        let scalar = Scalar::new(serde_json::json!({})).with_url("/scalar");
+        let router = Router::new().push(Router::with_path("api").push(Router::from(scalar)));
+        let service = Service::new(router);
+
+        let mut page = TestClient::get("http://localhost/api/scalar")
+            .send(&service)
+            .await;
+        assert_eq!(page.status_code, Some(salvo::http::StatusCode::OK));
+        let page = page.take_string().await.unwrap();
+        assert!(page.contains(r#"data-url="/api/scalar/api-docs/openapi.json""#));
+        assert!(page.contains(r#"src="/api/scalar/scalar-api-reference.js""#));
+
+        let mut spec = TestClient::get("http://localhost/api/scalar/api-docs/openapi.json")
+            .send(&service)
+            .await;
+        assert_eq!(spec.take_string().await.unwrap(), "{}");
+
+        let root = TestClient::get("http://localhost/scalar")
+            .send(&service)
+            .await;
+        assert_eq!(root.status_code, Some(salvo::http::StatusCode::NOT_FOUND));
+    }
+}
@@ -0,0 +1,721 @@
+use std::borrow::Cow;
+use std::sync::{Arc, OnceLock};
+
+use bytes::Bytes;
+use http::{HeaderValue, Method, Request, Response, StatusCode, header};
+use serde_json::Value;
+
+use crate::tenant::TenantCache;
+use crate::versioned::{VersionOf, Versions};
+use crate::{
+    CHANGELOG, OPENAPI_30_JSON, OPENAPI_JSON, SCALAR_API_REFERENCE_JS, SCALAR_SCRIPT, Scalar,
+    Serialize,
+};
+
+const TEXT_HTML: &str = "text/html; charset=utf-8";
+const APPLICATION_JAVASCRIPT: &str = "application/javascript";
+const APPLICATION_JSON: &str = "application/json";
+
+// The page and the spec are revalidated on every visit, the bundle only changes with the crate
+const NO_CACHE: &str = "no-cache";
+const MAX_AGE_ONE_DAY: &str = "public, max-age=86400";
+
+const ALLOW: &str = "GET, HEAD, OPTIONS";
+
+static SCRIPT: OnceLock<Asset> = OnceLock::new();
+
+// Set by adapters on requests whose path was stripped of the prefix the service is nested at
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Nested;
+
+// Set by adapters that route by the path of each resource, to the part of the path in front of it
+// that belongs to an enclosing scope or mount point
+#[derive(Debug, Clone)]
+pub(crate) struct Mounted(pub(crate) String);
+
+#[derive(Debug, Clone)]
+pub struct ScalarService {
+    inner: Arc<Resources>,
+}
+
+#[derive(Debug)]
+struct Resources {
+    url: String,
+    script_url: String,
+    api_json_url: String,
+    api_json_30_url: Option<String>,
+    changelog_url: Option<String>,
+    // Everything but the document, kept to render the page again below another base
+    page: Scalar<Value>,
+    markup: Asset,
+    api_json: Asset,
+    api_json_30: Option<Asset>,
+    changelog: Option<Asset>,
+    versions: Vec<(String, ScalarService)>,
+    // Where the root of versioned documentation sends the browser
+    redirect: Option<HeaderValue>,
+    // Tenant pages are rendered lazily, below the same base as this one
+    base: String,
+    tenants: Option<TenantCache>,
+}
+
+#[derive(Debug, Clone)]
+struct Asset {
+    content_type: &'static str,
+    cache_control: &'static str,
+    etag: HeaderValue,
+    body: Bytes,
+}
+
+impl Asset {
+    fn new(content_type: &'static str, cache_control: &'static str, body: Bytes) -> Self {
+        Self {
+            content_type,
+            cache_control,
+            etag: etag(&body),
+            body,
+        }
+    }
+
+    fn prebuilt(content_type: &'static str, body: &'static str, etag: &'static str) -> Self {
+        Self {
+            content_type,
+            cache_control: NO_CACHE,
+            etag: HeaderValue::from_static(etag),
+            body: Bytes::from_static(body.as_bytes()),
+        }
+    }
+
+    // Hashing the bundle is not free, so it is done once per process
+    fn script() -> &'static Self {
+        SCRIPT.get_or_init(|| {
+            let body = Bytes::from_static(SCALAR_API_REFERENCE_JS.as_bytes());
+            Self::new(APPLICATION_JAVASCRIPT, MAX_AGE_ONE_DAY, body)
+        })
+    }
+}
+
+impl ScalarService {
+    pub fn new<S: Serialize>(scalar: Scalar<S>) -> Self {
+        Self::mounted_at(scalar, "")
+    }
+
+    // Frameworks that mount routes below a base path only prefix the routes, so the urls baked
+    // into the page have to be prefixed here
+    pub(crate) fn mounted_at<S: Serialize>(scalar: Scalar<S>, base: &str) -> Self {
+        let api_json = match &scalar.prebuilt {
+            Some(prebuilt) => {
+                Asset::prebuilt(APPLICATION_JSON, prebuilt.api_json, prebuilt.api_json_etag)
+            }
+            None => Asset::new(APPLICATION_JSON, NO_CACHE, Bytes::from(scalar.api_json())),
+        };
+        let api_json_30 = (scalar.openapi_30 && scalar.versions.is_none()).then(|| {
+            Asset::new(
+                APPLICATION_JSON,
+                NO_CACHE,
+                Bytes::from(scalar.api_json_30()),
+            )
+        });
+        let markup = match scalar.prebuilt_markup() {
+            Some(prebuilt) if base.trim_end_matches('/').is_empty() => Some(Asset::prebuilt(
+                TEXT_HTML,
+                prebuilt.markup,
+                prebuilt.markup_etag,
+            )),
+            _ => None,
+        };
+        let page = Scalar {
+            url: scalar.url,
+            title: scalar.title,
+            openapi: Value::Null,
+            config: scalar.config,
+            spec_cors: scalar.spec_cors,
+            prebuilt: None,
+            lint_banner: scalar.lint_banner,
+            changelog: scalar.changelog,
+            versions: scalar.versions,
+            version_of: scalar.version_of,
+            tenants: scalar.tenants,
+            openapi_30: scalar.openapi_30,
+        };
+        Self::with_page(page, (api_json, api_json_30), base, markup)
+    }
+
+    pub fn rebased(&self, base: &str) -> Self {
+        let page = self.inner.page.clone();
+        let documents = (self.inner.api_json.clone(), self.inner.api_json_30.clone());
+        Self::with_page(page, documents, base, None)
+    }
+
+    fn with_page(
+        page: Scalar<Value>,
+        (api_json, api_json_30): (Asset, Option<Asset>),
+        base: &str,
+        markup: Option<Asset>,
+    ) -> Self {
+        let base = base.trim_end_matches('/');
+        let markup = markup.unwrap_or_else(|| {
+            let markup = page.render(
+                &format!("{base}{}", page.api_json_url()),
+                &format!("{base}{}", page.script_url()),
+            );
+            Asset::new(TEXT_HTML, NO_CACHE, Bytes::from(markup.0))
+        });
+        let changelog = page.changelog.as_ref().map(|changelog| {
+            let docs_url = match format!("{base}{}", page.url) {
+                url if url.is_empty() => "/".to_owned(),
+                url => url,
+            };
+            let markup = changelog.render(&page.title, &docs_url);
+            Asset::new(TEXT_HTML, NO_CACHE, Bytes::from(markup.0))
+        });
+        let url = page.url.trim_end_matches('/');
+        let versions = page
+            .versions
+            .as_ref()
+            .map(|versions| {
+                let links = versions
+                    .entries
+                    .iter()
+                    .map(|(name, _)| (name.clone(), format!("{base}{url}/{name}")))
+                    .collect::<Vec<_>>();
+                versions
+                    .entries
+                    .iter()
+                    .map(|(name, version)| {
+                        let mut version = version.clone();
+                        version.inherit(&page);
+                        version.url = Cow::Owned(format!("{url}/{name}"));
+                        version.version_of = Some(VersionOf {
+                            script_url: page.script_url(),
+                            current: name.clone(),
+                            versions: links.clone(),
+                        });
+                        (name.clone(), ScalarService::mounted_at(version, base))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let redirect = page
+            .versions
+            .as_ref()
+            .and_then(Versions::default_version)
+            .and_then(|name| HeaderValue::try_from(format!("{base}{url}/{name}")).ok());
+        let tenants = page.tenants.as_ref().map(TenantCache::new);
+        let resources = Resources {
+            url: page.url.clone().into_owned(),
+            script_url: page.script_url(),
+            api_json_url: page.api_json_url(),
+            api_json_30_url: api_json_30.as_ref().map(|_| page.api_json_30_url()),
+            changelog_url: changelog.as_ref().map(|_| page.changelog_url()),
+            page,
+            markup,
+            api_json,
+            api_json_30,
+            changelog,
+            versions,
+            redirect,
+            base: base.to_owned(),
+            tenants,
+        };
+        Self {
+            inner: Arc::new(resources),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.inner.url
+    }
+
+    pub fn script_url(&self) -> &str {
+        &self.inner.script_url
+    }
+
+    pub fn api_json_url(&self) -> &str {
+        &self.inner.api_json_url
+    }
+
+    pub fn api_json_30_url(&self) -> Option<&str> {
+        self.inner.api_json_30_url.as_deref()
+    }
+
+    pub fn changelog_url(&self) -> Option<&str> {
+        self.inner.changelog_url.as_deref()
+    }
+
+    // Every path the service answers, for frameworks that register their routes one by one
+    pub fn paths(&self) -> Vec<String> {
+        let mut paths = vec![self.url().to_owned(), self.script_url().to_owned()];
+        if self.inner.page.versions.is_none() {
+            paths.push(self.api_json_url().to_owned());
+        }
+        paths.extend(self.api_json_30_url().map(str::to_owned));
+        paths.extend(self.changelog_url().map(str::to_owned));
+        for (_, version) in &self.inner.versions {
+            for path in version.paths() {
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        // Tenants are only resolved on request, so every route one of them may serve is registered
+        if self.inner.tenants.is_some() {
+            let url = self.url().trim_end_matches('/');
+            for route in [OPENAPI_JSON, OPENAPI_30_JSON, CHANGELOG] {
+                let path = format!("{url}/{route}");
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        paths
+    }
+
+    pub fn handle(&self, request: &Request<()>) -> Option<Response<Bytes>> {
+        let path = request.uri().path();
+        match self.tenant(request) {
+            Some(tenant) => tenant?.handle_path(request, path),
+            None => self.handle_path(request, path),
+        }
+    }
+
+    // `None` when the request belongs to no tenant, and `Some(None)` when its tenant is unknown
+    // or hidden
+    fn tenant(&self, request: &Request<()>) -> Option<Option<ScalarService>> {
+        let tenants = self.inner.tenants.as_ref()?;
+        let tenant_id = tenants.tenant_id(request)?;
+        let service = tenants.service(&tenant_id, |mut scalar| {
+            scalar.url = self.inner.page.url.clone();
+            ScalarService::mounted_at(scalar, &self.inner.base)
+        });
+        Some(service)
+    }
+
+    fn handle_path(&self, request: &Request<()>, path: &str) -> Option<Response<Bytes>> {
+        let path = self.relative(request, path)?;
+        self.handle_relative(request, path)
+    }
+
+    fn handle_relative(&self, request: &Request<()>, path: &str) -> Option<Response<Bytes>> {
+        // The routes of the root come first, a version cannot hide the script the versions share
+        let Some(asset) = self.resolve(path) else {
+            let (version, rest) = self.version(path)?;
+            return version.handle_relative(request, rest);
+        };
+        // Only the spec is fetched from other origins, by client generators and other viewers
+        let is_spec = std::ptr::eq(asset, &self.inner.api_json)
+            || self
+                .inner
+                .api_json_30
+                .as_ref()
+                .is_some_and(|api_json_30| std::ptr::eq(asset, api_json_30));
+        let cors = self.inner.page.spec_cors.as_ref().filter(|_| is_spec);
+        let method = request.method();
+        if method == Method::OPTIONS {
+            let mut builder = Response::builder()
+                .status(StatusCode::NO_CONTENT)
+                .header(header::ALLOW, ALLOW);
+            if let Some(cors) = cors {
+                builder = cors.preflight(request, builder);
+            }
+            return Some(builder.body(Bytes::new()).unwrap());
+        }
+        if method != Method::GET && method != Method::HEAD {
+            let response = Response::builder()
+                .status(StatusCode::METHOD_NOT_ALLOWED)
+                .header(header::ALLOW, ALLOW)
+                .body(Bytes::new())
+                .unwrap();
+            return Some(response);
+        }
+        let redirect = self
+            .inner
+            .redirect
+            .as_ref()
+            .filter(|_| std::ptr::eq(asset, &self.inner.markup));
+        if let Some(location) = redirect {
+            let response = Response::builder()
+                .status(StatusCode::FOUND)
+                .header(header::LOCATION, location.clone())
+                .body(Bytes::new())
+                .unwrap();
+            return Some(response);
+        }
+        let mut builder = Response::builder()
+            .header(header::ETAG, asset.etag.clone())
+            .header(header::CACHE_CONTROL, asset.cache_control);
+        if let Some(cors) = cors {
+            builder = cors.apply(request, builder);
+        }
+        if is_fresh(request, &asset.etag) {
+            let response = builder
+                .status(StatusCode::NOT_MODIFIED)
+                .body(Bytes::new())
+                .unwrap();
+            return Some(response);
+        }
+        let builder = builder
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, asset.content_type)
+            .header(header::CONTENT_LENGTH, asset.body.len());
+        let body = if method == Method::HEAD {
+            Bytes::new()
+        } else {
+            asset.body.clone()
+        };
+        Some(builder.body(body).unwrap())
+    }
+
+    // The path is either the full path, or the path below the mount point when an adapter tells the
+    // service it is nested, e.g. through `nest_service`, which strips the prefix before calling it
+    fn relative<'a>(&self, request: &Request<()>, path: &'a str) -> Option<&'a str> {
+        let path = match request.extensions().get::<Mounted>() {
+            Some(Mounted(prefix)) => path.strip_prefix(prefix.as_str())?,
+            None => path,
+        };
+        let url = self.inner.url.trim_end_matches('/');
+        match path.strip_prefix(url) {
+            Some(rest) if rest.is_empty() || rest.starts_with('/') => Some(rest),
+            _ if request.extensions().get::<Nested>().is_some() => Some(path),
+            _ => None,
+        }
+    }
+
+    // A version gets the rest of the path below its name, which it resolves like a nested service
+    fn version<'a>(&self, path: &'a str) -> Option<(&ScalarService, &'a str)> {
+        let path = path.trim_start_matches('/');
+        let (name, rest) = path
+            .find('/')
+            .map_or((path, ""), |index| path.split_at(index));
+        self.inner
+            .versions
+            .iter()
+            .find(|(version, _)| version == name)
+            .map(|(_, service)| (service, rest))
+    }
+
+    fn resolve(&self, path: &str) -> Option<&Asset> {
+        let versions = self.inner.page.versions.as_ref();
+        match path.trim_start_matches('/') {
+            // Versioned documentation without any version has nothing to show
+            "" if versions.is_some_and(|versions| versions.entries.is_empty()) => None,
+            "" => Some(&self.inner.markup),
+            SCALAR_SCRIPT => Some(Asset::script()),
+            // The root of versioned documentation has no document of its own
+            OPENAPI_JSON if versions.is_none() => Some(&self.inner.api_json),
+            OPENAPI_30_JSON => self.inner.api_json_30.as_ref(),
+            CHANGELOG => self.inner.changelog.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl<S: Serialize> From<Scalar<S>> for ScalarService {
+    fn from(scalar: Scalar<S>) -> Self {
+        ScalarService::new(scalar)
+    }
+}
+
+impl<S: Serialize> Scalar<S> {
+    pub fn into_service(self) -> ScalarService {
+        ScalarService::new(self)
+    }
+}
+
+// Frameworks built on other versions of `http` hand over their requests piece by piece. They
+// also strip the body of HEAD responses themselves and work out the length from the full body,
+// so HEAD is passed on as GET. The uri is the original one, so tenants can be told apart by any
+// part of it, and `route` is the path the framework matched below its scope or mount point
+#[cfg(any(
+    feature = "actix-web",
+    feature = "ntex",
+    feature = "rocket",
+    feature = "salvo",
+    feature = "warp"
+))]
+pub(crate) fn to_request<'a>(
+    method: &str,
+    uri: &str,
+    route: &str,
+    authority: Option<&str>,
+    headers: impl Iterator<Item = (&'a str, &'a [u8])>,
+) -> Option<Request<()>> {
+    let method = if method == Method::HEAD {
+        "GET"
+    } else {
+        method
+    };
+    let mut builder = Request::builder().method(method).uri(uri);
+    for (name, value) in headers {
+        builder = builder.header(name, HeaderValue::from_bytes(value).ok()?);
+    }
+    let mut request = builder.body(()).ok()?;
+    // HTTP/2 sends the authority in place of a Host header
+    let authority = authority.filter(|_| !request.headers().contains_key(header::HOST));
+    if let Some(authority) = authority {
+        let host = HeaderValue::from_str(authority).ok()?;
+        request.headers_mut().insert(header::HOST, host);
+    }
+    let prefix = request.uri().path().strip_suffix(route)?;
+    if !prefix.is_empty() {
+        let mounted = Mounted(prefix.to_owned());
+        request.extensions_mut().insert(mounted);
+    }
+    Some(request)
+}
+
+fn is_fresh(request: &Request<()>, etag: &HeaderValue) -> bool {
+    let etag = etag.as_bytes();
+    request
+        .headers()
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/").as_bytes() == etag)
+}
+
+// FNV-1a, good enough to tell two versions of a document apart
+pub(crate) fn etag(body: &[u8]) -> HeaderValue {
+    let hash = body.iter().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    });
+    HeaderValue::try_from(format!("\"{hash:016x}\"")).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use utoipa::openapi::OpenApi;
+
+    use super::*;
+    use crate::CorsPolicy;
+    use crate::lint::lint;
+
+    fn request(method: Method, uri: &str, headers: &[(&str, &str)]) -> Request<()> {
+        let mut builder = Request::builder().method(method).uri(uri);
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        builder.body(()).unwrap()
+    }
+
+    fn get(service: &ScalarService, uri: &str) -> Option<Response<Bytes>> {
+        service.handle(&request(Method::GET, uri, &[]))
+    }
+
+    fn body(response: Option<Response<Bytes>>) -> String {
+        String::from_utf8(response.unwrap().into_body().to_vec()).unwrap()
+    }
+
+    fn openapi() -> OpenApi {
+        serde_json::from_value(json!({
+            "openapi": "3.1.0",
+            "info": { "title": "items", "version": "1" },
+            "paths": { "/items": { "get": { "responses": {} } } }
+        }))
+        .unwrap()
+    }
+
+    fn versioned() -> Scalar<Value> {
+        Scalar::versioned()
+            .with_url("/docs")
+            .with_version("v1", Scalar::new(json!({ "version": 1 })))
+            .with_version("v2", Scalar::new(json!({ "version": 2 })))
+    }
+
+    #[test]
+    fn versioned_root_redirects_to_the_default_version() {
+        let service = versioned().into_service();
+        let response = get(&service, "/docs").unwrap();
+        assert_eq!(response.status(), StatusCode::FOUND);
+        assert_eq!(response.headers()[header::LOCATION], "/docs/v1");
+
+        let service = versioned().with_default_version("v2").into_service();
+        let response = get(&service, "/docs").unwrap();
+        assert_eq!(response.headers()[header::LOCATION], "/docs/v2");
+
+        // A default that names no version is ignored
+        let service = versioned().with_default_version("v3").into_service();
+        let response = get(&service, "/docs").unwrap();
+        assert_eq!(response.headers()[header::LOCATION], "/docs/v1");
+    }
+
+    #[test]
+    fn versions_serve_their_own_documents_and_a_selector() {
+        let service = versioned().into_service();
+        assert_eq!(
+            body(get(&service, "/docs/v2/api-docs/openapi.json")),
+            r#"{"version":2}"#
+        );
+        let page = body(get(&service, "/docs/v2"));
+        assert!(page.contains(r#"data-url="/docs/v2/api-docs/openapi.json""#));
+        assert!(page.contains(r#"src="/docs/scalar-api-reference.js""#));
+        assert!(page.contains(r#"<option value="/docs/v1">v1</option>"#));
+        assert!(page.contains(r#"<option value="/docs/v2" selected>v2</option>"#));
+        // The root has no document of its own
+        assert!(get(&service, "/docs/api-docs/openapi.json").is_none());
+        assert!(get(&service, "/docs/v3").is_none());
+    }
+
+    #[test]
+    fn versioned_root_without_versions_is_not_found() {
+        let service = Scalar::versioned().with_url("/docs").into_service();
+        assert!(get(&service, "/docs").is_none());
+        assert!(get(&service, "/docs/api-docs/openapi.json").is_none());
+    }
+
+    #[test]
+    fn versions_inherit_the_settings_of_the_root() {
+        let openapi = openapi();
+        let service = versioned()
+            .with_spec_cors(CorsPolicy::any())
+            .with_openapi_30()
+            .with_lint_banner(lint(&openapi))
+            .with_history(vec![("1", openapi)])
+            .into_service();
+        let origin = [("origin", "https://example.com")];
+        let spec = request(Method::GET, "/docs/v1/api-docs/openapi.json", &origin);
+        let spec = service.handle(&spec).unwrap();
+        assert_eq!(spec.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+        let spec_30 = get(&service, "/docs/v1/api-docs/openapi-3.0.json").unwrap();
+        assert_eq!(spec_30.status(), StatusCode::OK);
+        assert!(body(get(&service, "/docs/v1")).contains("OpenAPI lint:"));
+        let changelog = body(get(&service, "/docs/v2/changelog"));
+        assert!(changelog.contains(r#"<a href="/docs/v2">Back to the documentation</a>"#));
+    }
+
+    #[test]
+    fn versions_keep_their_own_settings() {
+        let service = Scalar::versioned()
+            .with_url("/docs")
+            .with_spec_cors(CorsPolicy::any())
+            .with_version(
+                "v1",
+                Scalar::new(json!({}))
+                    .with_spec_cors(CorsPolicy::new().allow_origin("https://a.dev")),
+            )
+            .into_service();
+        let origin = [("origin", "https://a.dev")];
+        let spec = request(Method::GET, "/docs/v1/api-docs/openapi.json", &origin);
+        let spec = service.handle(&spec).unwrap();
+        assert_eq!(
+            spec.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://a.dev"
+        );
+    }
+
+    #[test]
+    fn versions_do_not_hide_the_routes_of_the_root() {
+        let service = Scalar::versioned()
+            .with_url("/docs")
+            .with_version(SCALAR_SCRIPT, Scalar::new(json!({})))
+            .with_version("api-docs", Scalar::new(json!({ "version": 1 })))
+            .into_service();
+        let script = get(&service, "/docs/scalar-api-reference.js").unwrap();
+        assert_eq!(
+            script.headers()[header::CONTENT_TYPE],
+            APPLICATION_JAVASCRIPT
+        );
+        assert_eq!(
+            body(get(&service, "/docs/api-docs/api-docs/openapi.json")),
+            r#"{"version":1}"#
+        );
+    }
+
+    #[test]
+    fn head_keeps_the_length_of_the_body() {
+        let service = Scalar::new(json!({ "openapi": "3.1.0" })).into_service();
+        let spec = request(Method::HEAD, "/api-docs/openapi.json", &[]);
+        let response = service.handle(&spec).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_LENGTH], "19");
+        assert_eq!(response.headers()[header::CONTENT_TYPE], APPLICATION_JSON);
+        assert!(response.headers().contains_key(header::ETAG));
+        assert!(response.body().is_empty());
+    }
+
+    #[test]
+    fn options_lists_the_allowed_methods() {
+        let service = Scalar::new(json!({})).into_service();
+        for path in ["/", "/api-docs/openapi.json", "/scalar-api-reference.js"] {
+            let response = service
+                .handle(&request(Method::OPTIONS, path, &[]))
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+            assert_eq!(response.headers()[header::ALLOW], ALLOW);
+            assert!(response.body().is_empty());
+        }
+    }
+
+    #[test]
+    fn other_methods_are_not_allowed() {
+        let service = Scalar::new(json!({})).into_service();
+        for method in [Method::POST, Method::PUT, Method::DELETE, Method::PATCH] {
+            let response = service
+                .handle(&request(method, "/api-docs/openapi.json", &[]))
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+            assert_eq!(response.headers()[header::ALLOW], ALLOW);
+        }
+        // Paths outside of the docs are left to the framework, whatever the method
+        assert!(
+            service
+                .handle(&request(Method::POST, "/items", &[]))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn cors_only_applies_to_the_spec() {
+        let policy = CorsPolicy::new().allow_origin("https://a.dev").max_age(600);
+        let service = Scalar::new(json!({}))
+            .with_openapi_30()
+            .with_spec_cors(policy)
+            .into_service();
+        let origin = ("origin", "https://a.dev");
+        for path in ["/api-docs/openapi.json", "/api-docs/openapi-3.0.json"] {
+            let response = service
+                .handle(&request(Method::GET, path, &[origin]))
+                .unwrap();
+            let headers = response.headers();
+            assert_eq!(
+                headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+                "https://a.dev"
+            );
+            assert_eq!(headers[header::VARY], "origin");
+
+            let preflight = [origin, ("access-control-request-method", "GET")];
+            let response = service
+                .handle(&request(Method::OPTIONS, path, &preflight))
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+            let headers = response.headers();
+            assert_eq!(
+                headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+                "https://a.dev"
+            );
+            assert_eq!(headers[header::ACCESS_CONTROL_MAX_AGE], "600");
+        }
+        for path in ["/", "/scalar-api-reference.js"] {
+            let response = service
+                .handle(&request(Method::GET, path, &[origin]))
+                .unwrap();
+            assert!(
+                !response
+                    .headers()
+                    .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            );
+            let preflight = [origin, ("access-control-request-method", "GET")];
+            let response = service
+                .handle(&request(Method::OPTIONS, path, &preflight))
+                .unwrap();
+            assert!(
+                !response
+                    .headers()
+                    .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            );
+        }
+    }
+}
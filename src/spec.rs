@@ -0,0 +1,179 @@
+use serde_json::{Map, Number, Value};
+use utoipa::openapi::path::{Operation, PathItem};
+use utoipa::openapi::schema::{ArrayItems, Schema, SchemaType, Type};
+use utoipa::openapi::{Components, OpenApi, RefOr};
+
+const SCHEMA_REF_PREFIX: &str = "#/components/schemas/";
+
+// Example generation stops here, recursive schemas would otherwise never end
+const MAX_EXAMPLE_DEPTH: usize = 8;
+
+pub(crate) fn path_operations(item: &PathItem) -> impl Iterator<Item = (&'static str, &Operation)> {
+    [
+        ("get", item.get.as_ref()),
+        ("put", item.put.as_ref()),
+        ("post", item.post.as_ref()),
+        ("delete", item.delete.as_ref()),
+        ("options", item.options.as_ref()),
+        ("head", item.head.as_ref()),
+        ("patch", item.patch.as_ref()),
+        ("trace", item.trace.as_ref()),
+    ]
+    .into_iter()
+    .filter_map(|(method, operation)| operation.map(|operation| (method, operation)))
+}
+
+pub(crate) fn path_operations_mut(
+    item: &mut PathItem,
+) -> impl Iterator<Item = (&'static str, &mut Operation)> {
+    [
+        ("get", item.get.as_mut()),
+        ("put", item.put.as_mut()),
+        ("post", item.post.as_mut()),
+        ("delete", item.delete.as_mut()),
+        ("options", item.options.as_mut()),
+        ("head", item.head.as_mut()),
+        ("patch", item.patch.as_mut()),
+        ("trace", item.trace.as_mut()),
+    ]
+    .into_iter()
+    .filter_map(|(method, operation)| operation.map(|operation| (method, operation)))
+}
+
+pub(crate) fn operations_mut(openapi: &mut OpenApi) -> impl Iterator<Item = &mut Operation> {
+    openapi
+        .paths
+        .paths
+        .values_mut()
+        .flat_map(|item| path_operations_mut(item).map(|(_, operation)| operation))
+}
+
+pub(crate) fn resolve_schema<'a>(
+    schema: &'a RefOr<Schema>,
+    components: Option<&'a Components>,
+) -> Option<&'a Schema> {
+    let mut schema = schema;
+    // Bounded, so a chain of references pointing at each other does not hang
+    for _ in 0..MAX_EXAMPLE_DEPTH {
+        match schema {
+            RefOr::T(schema) => return Some(schema),
+            RefOr::Ref(reference) => {
+                let name = reference.ref_location.strip_prefix(SCHEMA_REF_PREFIX)?;
+                schema = components?.schemas.get(name)?;
+            }
+        }
+    }
+    None
+}
+
+pub(crate) fn example_for_schema(schema: &RefOr<Schema>, components: Option<&Components>) -> Value {
+    example_at_depth(schema, components, 0)
+}
+
+fn example_at_depth(
+    schema: &RefOr<Schema>,
+    components: Option<&Components>,
+    depth: usize,
+) -> Value {
+    if depth > MAX_EXAMPLE_DEPTH {
+        return Value::Null;
+    }
+    let Some(schema) = resolve_schema(schema, components) else {
+        return Value::Null;
+    };
+    match schema {
+        Schema::Object(object) => {
+            if let Some(example) = object.examples.first() {
+                return example.clone();
+            }
+            #[allow(deprecated)]
+            if let Some(example) = object.example.as_ref() {
+                return example.clone();
+            }
+            if let Some(default) = object.default.as_ref() {
+                return default.clone();
+            }
+            if let Some(value) = object
+                .enum_values
+                .as_ref()
+                .and_then(|values| values.first())
+            {
+                return value.clone();
+            }
+            if !object.properties.is_empty() {
+                let properties = object
+                    .properties
+                    .iter()
+                    .map(|(name, property)| {
+                        let example = example_at_depth(property, components, depth + 1);
+                        (name.clone(), example)
+                    })
+                    .collect::<Map<_, _>>();
+                return Value::Object(properties);
+            }
+            example_for_type(&object.schema_type)
+        }
+        Schema::Array(array) => {
+            if let Some(example) = array.examples.first() {
+                return example.clone();
+            }
+            match &array.items {
+                ArrayItems::RefOrSchema(items) => {
+                    Value::Array(vec![example_at_depth(items, components, depth + 1)])
+                }
+                ArrayItems::False => Value::Array(Vec::new()),
+            }
+        }
+        Schema::OneOf(one_of) => match one_of.examples.first() {
+            Some(example) => example.clone(),
+            None => one_of
+                .items
+                .first()
+                .map(|item| example_at_depth(item, components, depth + 1))
+                .unwrap_or(Value::Null),
+        },
+        Schema::AnyOf(any_of) => match any_of.examples.first() {
+            Some(example) => example.clone(),
+            None => any_of
+                .items
+                .first()
+                .map(|item| example_at_depth(item, components, depth + 1))
+                .unwrap_or(Value::Null),
+        },
+        Schema::AllOf(all_of) => {
+            if let Some(example) = all_of.examples.first() {
+                return example.clone();
+            }
+            let mut merged = Map::new();
+            for item in &all_of.items {
+                match example_at_depth(item, components, depth + 1) {
+                    Value::Object(properties) => merged.extend(properties),
+                    other if all_of.items.len() == 1 => return other,
+                    _ => {}
+                }
+            }
+            Value::Object(merged)
+        }
+        _ => Value::Null,
+    }
+}
+
+fn example_for_type(schema_type: &SchemaType) -> Value {
+    let schema_type = match schema_type {
+        SchemaType::Type(schema_type) => schema_type,
+        SchemaType::Array(types) => match types.iter().find(|t| **t != Type::Null) {
+            Some(schema_type) => schema_type,
+            None => return Value::Null,
+        },
+        SchemaType::AnyValue => return Value::Null,
+    };
+    match schema_type {
+        Type::String => Value::String("string".to_owned()),
+        Type::Integer => Value::Number(Number::from(0)),
+        Type::Number => Value::Number(Number::from_f64(0.0).unwrap()),
+        Type::Boolean => Value::Bool(false),
+        Type::Array => Value::Array(Vec::new()),
+        Type::Object => Value::Object(Map::new()),
+        _ => Value::Null,
+    }
+}
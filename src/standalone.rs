@@ -0,0 +1,124 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use maud::{DOCTYPE, Markup, PreEscaped, html};
+
+use crate::{SCALAR_API_REFERENCE_JS, Scalar, Serialize};
+
+// Sequences that would end the script element early, or switch the html parser into the escaped
+// state where the real `</script>` no longer closes it
+const SCRIPT_BREAKERS: [&str; 3] = ["</script", "<script", "<!--"];
+
+impl<S: Serialize> Scalar<S> {
+    pub fn standalone_html(&self) -> String {
+        self.standalone_markup().0
+    }
+
+    pub fn export_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.standalone_html())
+    }
+
+    fn standalone_markup(&self) -> Markup {
+        let config = self.config_json();
+        let title = self.title.as_ref();
+        let api_json = escape_json(&self.api_json());
+        let script = escape_script(SCALAR_API_REFERENCE_JS);
+        html! {
+            (DOCTYPE)
+            head {
+                title { (title) }
+                meta charset="utf-8";
+                meta name="viewport" content="width=device-width, initial-scale=1.0";
+            }
+            body {
+                script id="api-reference" type="application/json" data-configuration=(config) {
+                    (PreEscaped(api_json))
+                }
+                script type="module" {
+                    (PreEscaped(script))
+                }
+            }
+        }
+    }
+}
+
+// `<` only shows up inside json strings, where the unicode escape is equivalent
+fn escape_json(json: &str) -> String {
+    json.replace('<', "\\u003c")
+}
+
+// `\x3C` means `<` in string, template and regular expression literals alike
+fn escape_script(script: &str) -> String {
+    let bytes = script.as_bytes();
+    let mut escaped = String::with_capacity(script.len());
+    let mut last = 0;
+    for (index, _) in script.match_indices('<') {
+        let rest = &bytes[index..];
+        let breaks = SCRIPT_BREAKERS.iter().any(|breaker| {
+            rest.len() >= breaker.len()
+                && rest[..breaker.len()].eq_ignore_ascii_case(breaker.as_bytes())
+        });
+        if breaks {
+            escaped.push_str(&script[last..index]);
+            escaped.push_str("\\x3C");
+            last = index + 1;
+        }
+    }
+    escaped.push_str(&script[last..]);
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    #[test]
+    fn json_cannot_close_the_script() {
+        for spec in [
+            r#"{"description":"</script><script>alert(1)</script>"}"#,
+            r#"{"description":"<!-- <script>"}"#,
+            r#"{"description":"</SCRIPT >"}"#,
+        ] {
+            let escaped = escape_json(spec);
+            assert!(!escaped.contains('<'));
+            let value = serde_json::from_str::<Value>(&escaped).unwrap();
+            assert_eq!(value, serde_json::from_str::<Value>(spec).unwrap());
+        }
+    }
+
+    #[test]
+    fn script_breakers_are_escaped_in_any_case() {
+        let script = r#"let a = "</script>"; let b = `<!--`; let c = '</SCRIPT'; let d = 1 < 2;"#;
+        assert_eq!(
+            escape_script(script),
+            r#"let a = "\x3C/script>"; let b = `\x3C!--`; let c = '\x3C/SCRIPT'; let d = 1 < 2;"#
+        );
+        let mixed = escape_script("<ScRiPt></sCrIpT>");
+        assert_eq!(mixed, r"\x3CScRiPt>\x3C/sCrIpT>");
+    }
+
+    #[test]
+    fn standalone_page_keeps_the_spec_intact() {
+        let spec = json!({
+            "openapi": "3.1.0",
+            "info": { "title": "</script><!--", "version": "1", "description": "</SCRIPT>" },
+            "paths": {}
+        });
+        let html = Scalar::new(spec.to_string()).standalone_html();
+        let lowercase = html.to_ascii_lowercase();
+        assert_eq!(lowercase.matches("</script").count(), 2);
+        assert!(!lowercase.contains("<!--"));
+        let start = html.find(r#"id="api-reference""#).unwrap();
+        let start = start + html[start..].find('>').unwrap() + 1;
+        let end = start + html[start..].find("</script>").unwrap();
+        let embedded = serde_json::from_str::<Value>(&html[start..end]).unwrap();
+        assert_eq!(embedded, spec);
+    }
+}
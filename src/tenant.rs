@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use http::{Request, header};
+
+use crate::service::ScalarService;
+use crate::{Scalar, Serialize};
+
+pub trait TenantResolver: Send + Sync {
+    // The host without its port by default, override it to pick a path segment or a header
+    fn tenant_id(&self, request: &Request<()>) -> Option<String> {
+        let host = request
+            .headers()
+            .get(header::HOST)
+            .and_then(|host| host.to_str().ok())
+            .or_else(|| request.uri().host())?;
+        let host = match host.rsplit_once(':') {
+            Some((host, port)) if port.bytes().all(|byte| byte.is_ascii_digit()) => host,
+            _ => host,
+        };
+        Some(host.to_ascii_lowercase())
+    }
+
+    // `None` for an unknown tenant, which is answered with not found and not cached
+    fn resolve(&self, tenant_id: &str) -> Option<Tenant>;
+}
+
+#[derive(Debug, Clone)]
+pub struct Tenant {
+    scalar: Option<Scalar<Box<str>>>,
+}
+
+impl Tenant {
+    pub fn new<S: Serialize>(scalar: Scalar<S>) -> Self {
+        Self {
+            scalar: Some(scalar.into_raw()),
+        }
+    }
+
+    // The tenant is known, but is not allowed to see any documentation
+    pub fn hidden() -> Self {
+        Self { scalar: None }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Tenants {
+    resolver: Arc<dyn TenantResolver>,
+}
+
+impl fmt::Debug for Tenants {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tenants").finish_non_exhaustive()
+    }
+}
+
+// Rendering a page and serializing a document is done once per tenant, the first time it is seen
+pub(crate) struct TenantCache {
+    resolver: Arc<dyn TenantResolver>,
+    services: RwLock<HashMap<String, Option<ScalarService>>>,
+}
+
+impl fmt::Debug for TenantCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TenantCache")
+            .field("services", &self.services)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TenantCache {
+    pub(crate) fn new(tenants: &Tenants) -> Self {
+        Self {
+            resolver: tenants.resolver.clone(),
+            services: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn tenant_id(&self, request: &Request<()>) -> Option<String> {
+        self.resolver.tenant_id(request)
+    }
+
+    pub(crate) fn service(
+        &self,
+        tenant_id: &str,
+        build: impl FnOnce(Scalar<Box<str>>) -> ScalarService,
+    ) -> Option<ScalarService> {
+        if let Some(service) = self.services.read().unwrap().get(tenant_id) {
+            return service.clone();
+        }
+        let tenant = self.resolver.resolve(tenant_id)?;
+        let service = tenant.scalar.map(build);
+        let mut services = self.services.write().unwrap();
+        services
+            .entry(tenant_id.to_owned())
+            .or_insert(service)
+            .clone()
+    }
+}
+
+impl<S: Serialize> Scalar<S> {
+    // Every tenant is served through the same routes, with its own document, title and config
+    pub fn with_tenants(mut self, resolver: impl TenantResolver + 'static) -> Self {
+        self.tenants = Some(Tenants {
+            resolver: Arc::new(resolver),
+        });
+        self
+    }
+}
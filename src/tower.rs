@@ -0,0 +1,93 @@
+use std::convert::Infallible;
+use std::future::{Ready, ready};
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http::{Extensions, Request, Response, StatusCode};
+use http_body_util::Full;
+use tower_service::Service;
+
+use crate::service::{Nested, ScalarService};
+
+// A plain `http` response, so hyper, tonic-web or any other tower stack can serve it without axum
+impl<B> Service<Request<B>> for ScalarService {
+    type Response = Response<Full<Bytes>>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let (parts, _) = request.into_parts();
+        let mut request = Request::from_parts(parts, ());
+        if is_nested(request.extensions()) {
+            request.extensions_mut().insert(Nested);
+        }
+        let response = match self.handle(&request) {
+            Some(response) => response.map(Full::new),
+            None => {
+                let mut response = Response::new(Full::default());
+                *response.status_mut() = StatusCode::NOT_FOUND;
+                response
+            }
+        };
+        ready(Ok(response))
+    }
+}
+
+// axum strips the prefix of a service mounted with `nest_service`, and records where it is nested
+#[cfg(feature = "axum")]
+fn is_nested(extensions: &Extensions) -> bool {
+    extensions.get::<axum::extract::NestedPath>().is_some()
+}
+
+#[cfg(not(feature = "axum"))]
+fn is_nested(_: &Extensions) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use http::header;
+
+    use super::*;
+    use crate::Scalar;
+
+    fn call(service: &mut ScalarService, uri: &str) -> Response<Full<Bytes>> {
+        let request = Request::get(uri).body(Full::<Bytes>::default()).unwrap();
+        service.call(request).into_inner().unwrap()
+    }
+
+    #[test]
+    fn serves_plain_http_responses() {
+        let mut service = Scalar::new(serde_json::json!({}))
+            .with_url("/scalar")
+            .into_service();
+        let page = call(&mut service, "/scalar");
+        assert_eq!(page.status(), StatusCode::OK);
+        assert_eq!(
+            page.headers()[header::CONTENT_TYPE],
+            "text/html; charset=utf-8"
+        );
+        let spec = call(&mut service, "/scalar/api-docs/openapi.json");
+        assert_eq!(spec.headers()[header::CONTENT_LENGTH], "2");
+        assert_eq!(call(&mut service, "/other").status(), StatusCode::NOT_FOUND);
+        // Without a nesting framework, the assets are only found below the docs url
+        assert_eq!(call(&mut service, "/").status(), StatusCode::NOT_FOUND);
+        let spec = "/api-docs/openapi.json";
+        assert_eq!(call(&mut service, spec).status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn versions_serve_raw_specs_that_are_not_json() {
+        let mut service = Scalar::versioned()
+            .with_url("/scalar")
+            .with_version("v1", Scalar::new("not json"))
+            .into_service();
+        let spec = call(&mut service, "/scalar/v1/api-docs/openapi.json");
+        assert_eq!(spec.status(), StatusCode::OK);
+        assert_eq!(spec.headers()[header::CONTENT_LENGTH], "8");
+    }
+}
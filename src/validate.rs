@@ -0,0 +1,319 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt;
+
+use serde_json::Value;
+use utoipa::openapi::OpenApi;
+use utoipa::openapi::path::ParameterIn;
+
+use crate::Scalar;
+use crate::spec::path_operations;
+
+const REF: &str = "$ref";
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    DanglingRef {
+        location: String,
+        reference: String,
+    },
+    DuplicateOperationId {
+        operation_id: String,
+        operations: Vec<String>,
+    },
+    MissingPathParameter {
+        operation: String,
+        parameter: String,
+    },
+    UnknownPathParameter {
+        operation: String,
+        parameter: String,
+    },
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::DanglingRef {
+                location,
+                reference,
+            } => write!(f, "{location}: `{reference}` does not point at anything"),
+            Diagnostic::DuplicateOperationId {
+                operation_id,
+                operations,
+            } => write!(
+                f,
+                "operation id `{operation_id}` is used by {}",
+                operations.join(", ")
+            ),
+            Diagnostic::MissingPathParameter {
+                operation,
+                parameter,
+            } => write!(
+                f,
+                "{operation}: path parameter `{parameter}` is in the template but not declared"
+            ),
+            Diagnostic::UnknownPathParameter {
+                operation,
+                parameter,
+            } => write!(
+                f,
+                "{operation}: path parameter `{parameter}` is declared but not in the template"
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ValidationError {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationError {
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the OpenAPI document has {} problem(s)",
+            self.diagnostics.len()
+        )?;
+        for diagnostic in &self.diagnostics {
+            write!(f, "\n  {diagnostic}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ValidationError {}
+
+impl Scalar<OpenApi> {
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        dangling_refs(&self.openapi, &mut diagnostics);
+        duplicate_operation_ids(&self.openapi, &mut diagnostics);
+        path_parameters(&self.openapi, &mut diagnostics);
+        diagnostics
+    }
+
+    pub fn validate(self) -> Result<Self, ValidationError> {
+        let diagnostics = self.diagnostics();
+        if diagnostics.is_empty() {
+            Ok(self)
+        } else {
+            Err(ValidationError { diagnostics })
+        }
+    }
+
+    pub fn validate_or_warn(self) -> Self {
+        for diagnostic in self.diagnostics() {
+            log::warn!("{diagnostic}");
+        }
+        self
+    }
+}
+
+// References can sit almost anywhere, walking the serialized document finds all of them
+fn dangling_refs(openapi: &OpenApi, diagnostics: &mut Vec<Diagnostic>) {
+    let document = serde_json::to_value(openapi).unwrap();
+    let mut stack = vec![(String::new(), &document)];
+    while let Some((location, value)) = stack.pop() {
+        match value {
+            Value::Object(object) => {
+                if let Some(Value::String(reference)) = object.get(REF) {
+                    // Only local references can be checked, external documents are not at hand
+                    let resolves = match reference.strip_prefix('#') {
+                        Some(pointer) => document.pointer(pointer).is_some(),
+                        None => true,
+                    };
+                    if !resolves {
+                        diagnostics.push(Diagnostic::DanglingRef {
+                            location: format!("#{location}"),
+                            reference: reference.clone(),
+                        });
+                    }
+                }
+                for (key, value) in object.iter().rev() {
+                    let key = key.replace('~', "~0").replace('/', "~1");
+                    stack.push((format!("{location}/{key}"), value));
+                }
+            }
+            Value::Array(items) => {
+                for (index, value) in items.iter().enumerate().rev() {
+                    stack.push((format!("{location}/{index}"), value));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn duplicate_operation_ids(openapi: &OpenApi, diagnostics: &mut Vec<Diagnostic>) {
+    let mut operations = BTreeMap::<&str, Vec<String>>::new();
+    for (path, item) in &openapi.paths.paths {
+        for (method, operation) in path_operations(item) {
+            if let Some(operation_id) = &operation.operation_id {
+                operations
+                    .entry(operation_id)
+                    .or_default()
+                    .push(format!("{} {path}", method.to_uppercase()));
+            }
+        }
+    }
+    for (operation_id, operations) in operations {
+        if operations.len() > 1 {
+            diagnostics.push(Diagnostic::DuplicateOperationId {
+                operation_id: operation_id.to_owned(),
+                operations,
+            });
+        }
+    }
+}
+
+fn path_parameters(openapi: &OpenApi, diagnostics: &mut Vec<Diagnostic>) {
+    for (path, item) in &openapi.paths.paths {
+        let template = template_parameters(path);
+        for (method, operation) in path_operations(item) {
+            let operation_name = format!("{} {path}", method.to_uppercase());
+            // Parameters of the path item apply to every operation below it
+            let declared = item
+                .parameters
+                .iter()
+                .chain(operation.parameters.iter())
+                .flatten()
+                .filter(|parameter| parameter.parameter_in == ParameterIn::Path)
+                .map(|parameter| parameter.name.as_str())
+                .collect::<BTreeSet<_>>();
+            for parameter in template.difference(&declared) {
+                diagnostics.push(Diagnostic::MissingPathParameter {
+                    operation: operation_name.clone(),
+                    parameter: (*parameter).to_owned(),
+                });
+            }
+            for parameter in declared.difference(&template) {
+                diagnostics.push(Diagnostic::UnknownPathParameter {
+                    operation: operation_name.clone(),
+                    parameter: (*parameter).to_owned(),
+                });
+            }
+        }
+    }
+}
+
+fn template_parameters(path: &str) -> BTreeSet<&str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|segment| segment.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn diagnostics(paths: Value, components: Value) -> Vec<Diagnostic> {
+        let openapi = serde_json::from_value(json!({
+            "openapi": "3.1.0",
+            "info": { "title": "items", "version": "1" },
+            "paths": paths,
+            "components": components
+        }))
+        .unwrap();
+        Scalar::new(openapi).diagnostics()
+    }
+
+    fn path_parameter(name: &str) -> Value {
+        json!({ "name": name, "in": "path", "required": true })
+    }
+
+    #[test]
+    fn dangling_schema_reference() {
+        let paths = json!({
+            "/items": {
+                "get": {
+                    "responses": {
+                        "200": {
+                            "description": "items",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/Missing" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        let components = json!({ "schemas": { "Item": { "type": "object" } } });
+        assert_eq!(
+            diagnostics(paths, components),
+            [Diagnostic::DanglingRef {
+                location: "#/paths/~1items/get/responses/200/content/application~1json/schema"
+                    .to_owned(),
+                reference: "#/components/schemas/Missing".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn duplicate_operation_id_across_paths() {
+        let paths = json!({
+            "/items": { "get": { "operationId": "list", "responses": {} } },
+            "/orders": { "get": { "operationId": "list", "responses": {} } }
+        });
+        assert_eq!(
+            diagnostics(paths, json!({})),
+            [Diagnostic::DuplicateOperationId {
+                operation_id: "list".to_owned(),
+                operations: vec!["GET /items".to_owned(), "GET /orders".to_owned()],
+            }]
+        );
+    }
+
+    #[test]
+    fn template_parameter_without_declaration() {
+        let paths = json!({ "/items/{id}": { "get": { "responses": {} } } });
+        assert_eq!(
+            diagnostics(paths, json!({})),
+            [Diagnostic::MissingPathParameter {
+                operation: "GET /items/{id}".to_owned(),
+                parameter: "id".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn declared_parameter_outside_the_template() {
+        let paths = json!({
+            "/items": {
+                "get": { "parameters": [path_parameter("id")], "responses": {} }
+            }
+        });
+        assert_eq!(
+            diagnostics(paths, json!({})),
+            [Diagnostic::UnknownPathParameter {
+                operation: "GET /items".to_owned(),
+                parameter: "id".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn parameters_of_the_path_item_count_for_every_operation() {
+        let paths = json!({
+            "/items/{id}": {
+                "parameters": [path_parameter("id")],
+                "get": { "responses": {} },
+                "delete": { "responses": {} }
+            }
+        });
+        assert_eq!(diagnostics(paths, json!({})), []);
+    }
+}
@@ -0,0 +1,133 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use maud::{DOCTYPE, Markup, html};
+use serde_json::Value;
+
+use crate::{SCALAR_SCRIPT, Scalar, Serialize};
+
+// Kept clear of the Scalar header, which already has its own controls on the left
+const SELECTOR_STYLE: &str = "position: fixed; top: 8px; right: 16px; z-index: 10000; \
+    padding: 4px 8px; font: 13px sans-serif;";
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Versions {
+    pub(crate) default: Option<String>,
+    // Each document is serialized once up front, so versions may come from different sources
+    pub(crate) entries: Vec<(String, Scalar<Box<str>>)>,
+}
+
+// What a version page knows about the documentation it belongs to
+#[derive(Debug, Clone)]
+pub(crate) struct VersionOf {
+    pub(crate) script_url: String,
+    pub(crate) current: String,
+    pub(crate) versions: Vec<(String, String)>,
+}
+
+impl Versions {
+    // A default that names no version falls back to the first one, like no default at all
+    pub(crate) fn default_version(&self) -> Option<&str> {
+        self.default
+            .as_deref()
+            .filter(|default| self.entries.iter().any(|(name, _)| name == default))
+            .or_else(|| self.entries.first().map(|(name, _)| name.as_str()))
+    }
+}
+
+impl Scalar<Box<str>> {
+    // What is set on the root of versioned documentation applies to every version that does not
+    // set it itself
+    pub(crate) fn inherit<S: Serialize>(&mut self, root: &Scalar<S>) {
+        if self.spec_cors.is_none() {
+            self.spec_cors = root.spec_cors.clone();
+        }
+        if self.lint_banner.is_none() {
+            self.lint_banner = root.lint_banner.clone();
+        }
+        if self.changelog.is_none() {
+            self.changelog = root.changelog.clone();
+        }
+        self.openapi_30 |= root.openapi_30;
+    }
+}
+
+impl Scalar<Value> {
+    pub fn versioned() -> Self {
+        let mut scalar = Scalar::new(Value::Null);
+        scalar.versions = Some(Versions::default());
+        scalar
+    }
+
+    // Every version is served below `{url}/{name}`, with its own page, document and settings
+    pub fn with_version<S: Serialize>(mut self, name: impl AsRef<str>, scalar: Scalar<S>) -> Self {
+        let version = scalar.into_raw();
+        let name = name.as_ref().trim_matches('/').to_owned();
+        let versions = self.versions.get_or_insert_with(Versions::default);
+        versions.entries.retain(|(existing, _)| *existing != name);
+        versions.entries.push((name, version));
+        self
+    }
+
+    pub fn with_default_version(mut self, name: impl AsRef<str>) -> Self {
+        let name = name.as_ref().trim_matches('/').to_owned();
+        self.versions.get_or_insert_with(Versions::default).default = Some(name);
+        self
+    }
+}
+
+impl<S: Serialize> Scalar<S> {
+    // Every version is written below its name, next to the script they share, and the root sends
+    // the browser on to the default version
+    pub(crate) fn export_versions(&self, versions: &Versions, dir: &Path) -> io::Result<()> {
+        let Some(default) = versions.default_version() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "versioned documentation without any version cannot be exported",
+            ));
+        };
+        let links = versions
+            .entries
+            .iter()
+            .map(|(name, _)| (name.clone(), format!("../{name}/")))
+            .collect::<Vec<_>>();
+        let script_src = format!("../{SCALAR_SCRIPT}");
+        for (name, version) in &versions.entries {
+            let mut version = version.clone();
+            version.inherit(self);
+            version.version_of = Some(VersionOf {
+                script_url: script_src.clone(),
+                current: name.clone(),
+                versions: links.clone(),
+            });
+            version.export_page(&dir.join(name), &script_src)?;
+        }
+        let location = format!("{default}/");
+        let markup = html! {
+            (DOCTYPE)
+            head {
+                title { (self.title) }
+                meta charset="utf-8";
+                meta http-equiv="refresh" content={ "0; url=" (location) };
+            }
+            body {
+                a href=(location) { (default) }
+            }
+        };
+        fs::write(dir.join("index.html"), markup.0)
+    }
+
+    pub(crate) fn version_selector_markup(&self) -> Markup {
+        html! {
+            @if let Some(version_of) = &self.version_of {
+                select style=(SELECTOR_STYLE) aria-label="API version"
+                    onchange="window.location.href = this.value" {
+                    @for (name, href) in &version_of.versions {
+                        option value=(href) selected[*name == version_of.current] { (name) }
+                    }
+                }
+            }
+        }
+    }
+}
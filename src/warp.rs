@@ -0,0 +1,112 @@
+use warp::filters::path::FullPath;
+use warp::host::Authority;
+use warp::http::{HeaderMap, Method, Response, StatusCode};
+use warp::hyper::Body;
+use warp::{Filter, Rejection, Reply};
+
+use crate::service::to_request;
+use crate::{Scalar, Serialize};
+
+impl<S: Serialize> Scalar<S> {
+    pub fn into_filter(self) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+        let service = self.into_service();
+        // The full path is looked up, so anything outside of the docs url is rejected and left to
+        // the other filters
+        warp::get()
+            .or(warp::head())
+            .unify()
+            .or(warp::options())
+            .unify()
+            .and(warp::method())
+            .and(warp::path::full())
+            .and(warp::query::raw().or(warp::any().map(String::new)).unify())
+            .and(warp::host::optional())
+            .and(warp::header::headers_cloned())
+            .and_then(
+                move |method: Method,
+                      path: FullPath,
+                      query: String,
+                      authority: Option<Authority>,
+                      headers: HeaderMap| {
+                    let service = service.clone();
+                    async move {
+                        let uri = match query.as_str() {
+                            "" => path.as_str().to_owned(),
+                            query => format!("{}?{query}", path.as_str()),
+                        };
+                        let authority = authority.as_ref().map(Authority::as_str);
+                        let headers = headers
+                            .iter()
+                            .map(|(name, value)| (name.as_str(), value.as_bytes()));
+                        let route = path.as_str();
+                        let response = to_request(method.as_str(), &uri, route, authority, headers)
+                            .and_then(|request| service.handle(&request))
+                            .ok_or_else(warp::reject::not_found)?;
+                        let mut builder = Response::builder()
+                            .status(StatusCode::from_u16(response.status().as_u16()).unwrap());
+                        for (name, value) in response.headers() {
+                            if name == http::header::CONTENT_LENGTH {
+                                continue;
+                            }
+                            builder = builder.header(name.as_str(), value.as_bytes());
+                        }
+                        let body = Body::from(response.into_body());
+                        Ok::<_, Rejection>(builder.body(body).unwrap())
+                    }
+                },
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use warp::http::StatusCode;
+
+    use crate::Scalar;
+    use crate::service::{Mounted, to_request};
+
+    #[tokio::test]
+    async fn only_matches_below_the_docs_url() {
+        let filter = Scalar::new(serde_json::json!({}))
+            .with_url("/scalar")
+            .into_filter();
+        let status = |path: &'static str| {
+            let filter = filter.clone();
+            async move {
+                warp::test::request()
+                    .path(path)
+                    .reply(&filter)
+                    .await
+                    .status()
+            }
+        };
+        assert_eq!(status("/scalar").await, StatusCode::OK);
+        assert_eq!(
+            status("/scalar/api-docs/openapi.json").await,
+            StatusCode::OK
+        );
+        assert_eq!(status("/").await, StatusCode::NOT_FOUND);
+        assert_eq!(
+            status("/api-docs/openapi.json").await,
+            StatusCode::NOT_FOUND
+        );
+        let matched = warp::test::request().path("/todos").matches(&filter).await;
+        assert!(!matched);
+    }
+
+    #[test]
+    fn authority_stands_in_for_a_missing_host() {
+        let uri = "/docs/scalar?theme=moon";
+        let authority = Some("acme.example.com");
+        let request = to_request("GET", uri, "/scalar", authority, std::iter::empty()).unwrap();
+        assert_eq!(request.headers()[http::header::HOST], "acme.example.com");
+        assert_eq!(request.uri(), uri);
+        let Some(Mounted(prefix)) = request.extensions().get() else {
+            panic!("the mount point is not recorded");
+        };
+        assert_eq!(prefix, "/docs");
+        let host = [("host", "globex.example.com".as_bytes())];
+        let request = to_request("GET", uri, "/scalar", authority, host.into_iter()).unwrap();
+        assert_eq!(request.headers()[http::header::HOST], "globex.example.com");
+    }
+}
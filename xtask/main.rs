@@ -1,5 +1,6 @@
-use crate::task::upgrade_scalar_api_reference;
+use crate::task::{diff_openapi, upgrade_scalar_api_reference};
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 mod task;
 
@@ -14,11 +15,19 @@ struct Cli {
 enum Action {
     #[command(about = "Convert the json file to a binary file in rkyv format.")]
     UpgradeScalarApiReference,
+    #[command(about = "Compare two OpenAPI json documents and report the breaking changes.")]
+    DiffOpenapi {
+        old: PathBuf,
+        new: PathBuf,
+        #[arg(long, help = "Print the report as json")]
+        json: bool,
+    },
 }
 
 fn main() {
     let cli = Cli::parse();
     match cli.action {
         Action::UpgradeScalarApiReference => upgrade_scalar_api_reference(),
+        Action::DiffOpenapi { old, new, json } => diff_openapi(&old, &new, json),
     }
 }
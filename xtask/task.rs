@@ -1,6 +1,9 @@
 use std::fs::File;
 use std::io::BufReader;
+use std::path::Path;
 use std::process::{Command, Output};
+use utoipa::openapi::OpenApi;
+use utoipa_scalar_warpper::diff::diff;
 
 pub fn upgrade_scalar_api_reference() {
     // If the directory where the static file is located does not exist, install it directly
@@ -33,6 +36,28 @@ pub fn upgrade_scalar_api_reference() {
     }
 }
 
+pub fn diff_openapi(old: &Path, new: &Path, json: bool) {
+    let report = diff(&read_openapi(old), &read_openapi(new));
+    if json {
+        println!("{}", report.to_json());
+    } else if report.is_empty() {
+        println!("No changes.");
+    } else {
+        print!("{report}");
+    }
+    // A non-zero exit lets CI fail on breaking changes
+    if report.has_breaking() {
+        std::process::exit(1);
+    }
+}
+
+fn read_openapi(path: &Path) -> OpenApi {
+    let file =
+        File::open(path).unwrap_or_else(|err| panic!("Failed to open {}: {err}", path.display()));
+    serde_json::from_reader(BufReader::new(file))
+        .unwrap_or_else(|err| panic!("Failed to parse {}: {err}", path.display()))
+}
+
 fn get_scalar_version_from_package_json() -> Option<String> {
     let package_json = std::env::current_dir()
         .expect("Failed to get the current directory")